@@ -11,7 +11,7 @@ use crate::tag_utils::SwfSlice;
 
 #[cfg(test)]
 #[macro_use]
-mod test_utils;
+pub(crate) mod test_utils;
 
 #[macro_use]
 pub mod function;
@@ -121,6 +121,10 @@ pub struct Avm1<'gc> {
     /// Used to prevent scrolling on web.
     has_mouse_listener: bool,
 
+    /// Arrays currently being joined, so that an array containing itself
+    /// joins to a finite string.
+    joining_arrays: Vec<Object<'gc>>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -146,6 +150,7 @@ impl<'gc> Avm1<'gc> {
             halted: false,
             max_recursion_depth: 255,
             has_mouse_listener: false,
+            joining_arrays: vec![],
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
@@ -391,6 +396,11 @@ impl<'gc> Avm1<'gc> {
         self.stack.push(value);
     }
 
+    /// The number of values currently on the stack.
+    fn stack_len(&self) -> usize {
+        self.stack.len()
+    }
+
     #[allow(clippy::let_and_return)]
     fn pop(&mut self) -> Value<'gc> {
         let value = self.stack.pop().unwrap_or_else(|| {
@@ -1,7 +1,7 @@
 use crate::avm1::callable_value::CallableValue;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Avm1Function, ExecutionReason, FunctionObject};
-use crate::avm1::object::{Object, TObject};
+use crate::avm1::object::{array_index, Object, TObject};
 use crate::avm1::property::Attribute;
 use crate::avm1::scope::Scope;
 use crate::avm1::{
@@ -1469,9 +1469,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             // InitArray pops no args and pushes undefined if num_elements is out of range.
             Value::Undefined
         } else {
-            let array = ScriptObject::array(
+            let array = ScriptObject::array_with_capacity(
                 self.context.gc_context,
                 Some(self.context.avm1.prototypes.array),
+                (num_elements as usize).min(self.context.avm1.stack_len()),
             );
             for i in 0..num_elements as usize {
                 array.set_array_element(i, self.context.avm1.pop(), self.context.gc_context);
@@ -1489,16 +1490,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             // InitArray pops no args and pushes undefined if num_props is out of range.
             Value::Undefined
         } else {
-            let object = ScriptObject::object(
+            let capacity = (num_props as usize).min(self.context.avm1.stack_len() / 2);
+            let object = ScriptObject::object_with_capacity(
                 self.context.gc_context,
                 Some(self.context.avm1.prototypes.object),
+                capacity,
             );
+            let mut plain = Vec::with_capacity(capacity);
             for _ in 0..num_props as usize {
                 let value = self.context.avm1.pop();
                 let name_val = self.context.avm1.pop();
                 let name = name_val.coerce_to_string(self)?;
-                object.set(&name, value, self)?;
+                if self.is_plain_property(object.into(), &name) {
+                    plain.push((name, value));
+                } else {
+                    // Names with special behaviour go through `set`, after everything before them.
+                    self.define_plain_properties(object, &mut plain);
+                    object.set(&name, value, self)?;
+                }
             }
+            self.define_plain_properties(object, &mut plain);
             Value::Object(object.into())
         };
 
@@ -1506,6 +1517,48 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Whether assigning `name` on `object` would just store a value, so that it can be
+    /// defined directly instead of going through `set`.
+    ///
+    /// This is not the case for empty names, `__proto__`, array indices, `length`, or names
+    /// with a virtual property anywhere in the prototype chain.
+    fn is_plain_property(&mut self, object: Object<'gc>, name: &str) -> bool {
+        let is_special = if self.is_case_sensitive() {
+            name == "__proto__" || name == "length"
+        } else {
+            name.eq_ignore_ascii_case("__proto__") || name.eq_ignore_ascii_case("length")
+        };
+        if name.is_empty() || is_special || array_index(name).is_some() {
+            return false;
+        }
+
+        let mut proto = Value::Object(object);
+        while let Value::Object(this_proto) = proto {
+            if this_proto.has_own_virtual(self, name) {
+                return false;
+            }
+            proto = this_proto.proto();
+        }
+        true
+    }
+
+    /// Define the pending plain properties of an object literal in one go.
+    fn define_plain_properties(
+        &mut self,
+        object: ScriptObject<'gc>,
+        plain: &mut Vec<(AvmString<'gc>, Value<'gc>)>,
+    ) {
+        if plain.is_empty() {
+            return;
+        }
+        let entries: Vec<_> = plain
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value, Attribute::empty()))
+            .collect();
+        object.define_values(self.context.gc_context, &entries, self.is_case_sensitive());
+        plain.clear();
+    }
+
     fn action_implements_op(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let constructor = self.context.avm1.pop().coerce_to_object(self);
         let count = self.context.avm1.pop();
@@ -2844,11 +2897,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             )
             .into();
 
-            level.set_depth(self.context.gc_context, level_id as i32);
+            let stage = self.context.stage;
+            stage.set_level(&mut self.context, level_id as u32, level);
             level.set_default_root_name(&mut self.context);
-            self.context
-                .stage
-                .replace_at_depth(&mut self.context, level, level_id);
             level.post_instantiation(&mut self.context, level, None, Instantiator::Movie, false);
 
             level
@@ -3047,3 +3098,63 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::function::Executable;
+    use crate::avm1::test_utils::with_avm;
+
+    #[test]
+    fn init_object_sets_special_names() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            // An inherited setter that stores twice the assigned value under another name.
+            let getter = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok(Value::Undefined)),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let setter = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, this, args| {
+                    let value = args.get(0).unwrap().coerce_to_f64(activation)?;
+                    this.set("doubled", (value * 2.0).into(), activation)?;
+                    Ok(Value::Undefined)
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            activation.context.avm1.prototypes.object.add_property(
+                activation.context.gc_context,
+                "virtual",
+                getter,
+                Some(setter),
+                Attribute::empty(),
+            );
+
+            // `{plain: true, 0: "a", virtual: 2, length: 3}`
+            let properties: [(&'static str, Value<'_>); 4] = [
+                ("plain", true.into()),
+                ("0", "a".into()),
+                ("virtual", 2.into()),
+                ("length", 3.into()),
+            ];
+            for (name, value) in properties.iter() {
+                activation.context.avm1.push(*name);
+                activation.context.avm1.push(*value);
+            }
+            activation.context.avm1.push(properties.len() as f64);
+            activation.action_init_object()?;
+            let object = activation.context.avm1.pop().coerce_to_object(activation);
+
+            assert_eq!(object.get("plain", activation)?, true.into());
+            assert_eq!(object.get("0", activation)?, "a".into());
+            assert!(!object.has_own_property(activation, "virtual"));
+            assert_eq!(object.get("doubled", activation)?, 4.into());
+            assert_eq!(object.get("length", activation)?, 3.into());
+            assert_eq!(object.length(), 3);
+            Ok(())
+        });
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::object::script_object::Watcher;
 use crate::avm1::object::super_object::SuperObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::scope::Scope;
@@ -727,7 +728,7 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
         get: Object<'gc>,
         set: Option<Object<'gc>>,
         attributes: Attribute,
-    ) {
+    ) -> bool {
         self.base
             .add_property(gc_context, name, get, set, attributes)
     }
@@ -739,7 +740,7 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
         get: Object<'gc>,
         set: Option<Object<'gc>>,
         attributes: Attribute,
-    ) {
+    ) -> bool {
         self.base
             .add_property_with_case(activation, name, get, set, attributes)
     }
@@ -750,14 +751,18 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
         name: Cow<str>,
         callback: Object<'gc>,
         user_data: Value<'gc>,
-    ) {
-        self.base.set_watcher(activation, name, callback, user_data);
+    ) -> Option<Watcher<'gc>> {
+        self.base.set_watcher(activation, name, callback, user_data)
     }
 
     fn remove_watcher(&self, activation: &mut Activation<'_, 'gc, '_>, name: Cow<str>) -> bool {
         self.base.remove_watcher(activation, name)
     }
 
+    fn clear_watchers(&self, gc_context: MutationContext<'gc, '_>) {
+        self.base.clear_watchers(gc_context)
+    }
+
     fn has_property(&self, activation: &mut Activation<'_, 'gc, '_>, name: &str) -> bool {
         self.base.has_property(activation, name)
     }
@@ -778,6 +783,10 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
         self.base.get_keys(activation)
     }
 
+    fn own_enumerable_keys(&self, activation: &mut Activation<'_, 'gc, '_>) -> Vec<String> {
+        self.base.own_enumerable_keys(activation)
+    }
+
     fn type_of(&self) -> &'static str {
         TYPE_OF_FUNCTION
     }
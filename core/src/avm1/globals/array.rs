@@ -5,6 +5,7 @@ use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
+use crate::ecma_conversions::f64_to_wrapping_u32;
 use bitflags::bitflags;
 use gc_arena::MutationContext;
 use std::cmp::Ordering;
@@ -42,6 +43,10 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "toString" => method(to_string; DONT_ENUM);
     "sort" => method(sort; DONT_ENUM);
     "sortOn" => method(sort_on; DONT_ENUM);
+    "some" => method(some; DONT_ENUM);
+    "every" => method(every; DONT_ENUM);
+    "reduce" => method(reduce; DONT_ENUM);
+    "reduceRight" => method(reduce_right; DONT_ENUM);
 };
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
@@ -81,6 +86,11 @@ pub fn constructor<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let mut consumed = false;
 
+    // `Array.call(object)` turns a plain object into an array.
+    if let Some(array) = this.as_script_object() {
+        array.promote_to_array(activation.context.gc_context);
+    }
+
     if args.len() == 1 {
         let arg = args.get(0).unwrap();
         if let Value::Number(length) = *arg {
@@ -95,8 +105,12 @@ pub fn constructor<'gc>(
     }
 
     if !consumed {
-        for (i, arg) in args.iter().enumerate() {
-            this.set_array_element(i, arg.to_owned(), activation.context.gc_context);
+        if let Some(array) = this.as_script_object() {
+            array.set_array_elements(0, args, activation.context.gc_context);
+        } else {
+            for (i, arg) in args.iter().enumerate() {
+                this.set_array_element(i, arg.to_owned(), activation.context.gc_context);
+            }
         }
     }
 
@@ -109,31 +123,25 @@ pub fn array_function<'gc>(
     _this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let mut consumed = false;
-
     let prototype = activation.context.avm1.prototypes.array;
-    let array_obj = prototype.create_bare_object(activation, prototype)?;
 
-    if args.len() == 1 {
-        let arg = args.get(0).unwrap();
-        if let Value::Number(length) = *arg {
-            if length >= 0.0 {
-                array_obj.set_length(activation.context.gc_context, length as usize);
-                consumed = true;
-            } else if !length.is_nan() {
-                array_obj.set_length(activation.context.gc_context, 0);
-                consumed = true;
-            }
+    if let [Value::Number(length)] = args {
+        if !length.is_nan() {
+            let array_obj = ScriptObject::array(activation.context.gc_context, Some(prototype));
+            array_obj.set_length(activation.context.gc_context, length.max(0.0) as usize);
+            return Ok(array_obj.into());
         }
     }
 
-    if !consumed {
-        for (i, arg) in args.iter().enumerate() {
-            array_obj.set_array_element(i, arg.to_owned(), activation.context.gc_context);
-        }
-    }
+    Ok(
+        ScriptObject::array_with_elements(activation.context.gc_context, Some(prototype), args)
+            .into(),
+    )
+}
 
-    Ok(array_obj.into())
+/// Wraps an array length into the `uint32` range, as Flash does.
+fn wrap_length(length: usize) -> usize {
+    f64_to_wrapping_u32(length as f64) as usize
 }
 
 pub fn push<'gc>(
@@ -142,15 +150,23 @@ pub fn push<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let old_length = this.length();
-    let new_length = old_length + args.len();
-    this.set_length(activation.context.gc_context, new_length);
+    let new_length = wrap_length(old_length + args.len());
 
-    for i in 0..args.len() {
-        this.set_array_element(
-            old_length + i,
-            args.get(i).unwrap().to_owned(),
-            activation.context.gc_context,
-        );
+    if let Some(array) = this.as_script_object() {
+        // Write all elements at once, so that `length` is only synced once.
+        array.set_array_elements(old_length, args, activation.context.gc_context);
+        if array.length() != new_length {
+            array.set_length(activation.context.gc_context, new_length);
+        }
+    } else {
+        this.set_length(activation.context.gc_context, new_length);
+        for i in 0..args.len() {
+            this.set_array_element(
+                old_length + i,
+                args.get(i).unwrap().to_owned(),
+                activation.context.gc_context,
+            );
+        }
     }
 
     Ok((new_length as f64).into())
@@ -185,6 +201,7 @@ pub fn unshift<'gc>(
         );
     }
 
+    let new_length = wrap_length(new_length);
     this.set_length(activation.context.gc_context, new_length);
 
     Ok((new_length as f64).into())
@@ -242,11 +259,26 @@ pub fn reverse<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let length = this.length();
-    let mut values = this.array().to_vec();
+    // Holes are reversed along with the elements, whatever the array's storage.
+    let elements: Vec<Option<Value<'gc>>> = (0..this.length())
+        .map(|i| {
+            if this.has_own_property(activation, &i.to_string()) {
+                Some(this.array_element(i))
+            } else {
+                None
+            }
+        })
+        .collect();
 
-    for i in 0..length {
-        this.set_array_element(i, values.pop().unwrap(), activation.context.gc_context);
+    for (i, element) in elements.into_iter().rev().enumerate() {
+        match element {
+            Some(value) => {
+                this.set_array_element(i, value, activation.context.gc_context);
+            }
+            None => {
+                this.delete(activation, &i.to_string());
+            }
+        }
     }
 
     // Some docs incorrectly say reverse returns Void.
@@ -258,25 +290,146 @@ pub fn join<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    // An undefined separator is treated as missing, while an empty string joins with no delimiter.
     let separator = args
         .get(0)
+        .filter(|v| !matches!(v, Value::Undefined))
         .and_then(|v| v.coerce_to_string(activation).ok())
         .unwrap_or_else(|| ",".into());
-    let values: Vec<Value<'gc>> = this.array();
+    // An array nested within itself joins to an empty string.
+    let joining_arrays = &activation.context.avm1.joining_arrays;
+    if joining_arrays.iter().any(|o| Object::ptr_eq(*o, this)) {
+        return Ok("".into());
+    }
+    activation.context.avm1.joining_arrays.push(this);
 
-    Ok(AvmString::new(
-        activation.context.gc_context,
-        values
-            .iter()
-            .map(|v| {
-                v.coerce_to_string(activation)
-                    .unwrap_or_else(|_| "undefined".into())
-                    .to_string()
-            })
-            .collect::<Vec<String>>()
-            .join(&separator),
-    )
-    .into())
+    // Elements are fetched one at a time rather than borrowing the storage,
+    // since `toString` may run ActionScript that modifies the array.
+    let length = this.length();
+    let mut parts = Vec::with_capacity(length);
+    for i in 0..length {
+        // `undefined` and `null` elements are rendered as empty strings.
+        let part = match this.array_element(i) {
+            Value::Undefined | Value::Null => String::new(),
+            element => element
+                .coerce_to_string(activation)
+                .unwrap_or_else(|_| "undefined".into())
+                .to_string(),
+        };
+        parts.push(part);
+    }
+    activation.context.avm1.joining_arrays.pop();
+
+    Ok(AvmString::new(activation.context.gc_context, parts.join(&separator)).into())
+}
+
+/// Implements `Array.prototype.some`.
+///
+/// Returns `true` as soon as the callback returns a truthy value for an element.
+pub fn some<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(find_callback_result(activation, this, args, true)?.into())
+}
+
+/// Implements `Array.prototype.every`.
+///
+/// Returns `false` as soon as the callback returns a falsy value for an element.
+pub fn every<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((!find_callback_result(activation, this, args, false)?).into())
+}
+
+/// Calls `callback(element, index, array)` for each element of the array, stopping at the first
+/// element whose result coerces to `expected`. Returns whether such an element was found.
+fn find_callback_result<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    expected: bool,
+) -> Result<bool, Error<'gc>> {
+    let callback = args.get(0).copied().unwrap_or(Value::Undefined);
+    let this_arg = args
+        .get(1)
+        .copied()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation);
+
+    let length = this.length();
+    for i in 0..length {
+        let element = this.array_element(i);
+        let result = callback.call(
+            "[Callback]",
+            activation,
+            this_arg,
+            None,
+            &[element, i.into(), this.into()],
+        )?;
+        if result.as_bool(activation.swf_version()) == expected {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Implements `Array.prototype.reduce`.
+pub fn reduce<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let length = this.length();
+    reduce_elements(activation, this, args, 0..length)
+}
+
+/// Implements `Array.prototype.reduceRight`.
+pub fn reduce_right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let length = this.length();
+    reduce_elements(activation, this, args, (0..length).rev())
+}
+
+/// Folds the elements at `indices` with `callback(accumulator, element, index, array)`.
+///
+/// Holes are skipped. Without an initial value, the first element visited seeds the
+/// accumulator, and reducing an array with no elements throws.
+fn reduce_elements<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    indices: impl Iterator<Item = usize>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let callback = args.get(0).copied().unwrap_or(Value::Undefined);
+    let this_arg = Value::Undefined.coerce_to_object(activation);
+
+    let mut accumulator = args.get(1).copied();
+    for i in indices {
+        if !this.has_own_property(activation, &i.to_string()) {
+            continue;
+        }
+        let element = this.array_element(i);
+        accumulator = Some(match accumulator {
+            Some(accumulator) => callback.call(
+                "[Callback]",
+                activation,
+                this_arg,
+                None,
+                &[accumulator, element, i.into(), this.into()],
+            )?,
+            None => element,
+        });
+    }
+
+    accumulator
+        .ok_or_else(|| Error::ThrownValue("Reduce of empty array with no initial value".into()))
 }
 
 /// Handles an index parameter that may be positive (starting from beginning) or negaitve (starting from end).
@@ -290,6 +443,26 @@ fn make_index_absolute(index: i32, length: usize) -> usize {
     }
 }
 
+/// Copies the elements of `source` in `range` into `target`, starting at `target_start`.
+/// Holes in `source` are skipped, so they remain holes in `target`.
+fn copy_array_elements<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    source: Object<'gc>,
+    range: std::ops::Range<usize>,
+    target: Object<'gc>,
+    target_start: usize,
+) {
+    for (i, index) in range.enumerate() {
+        if source.has_own_property(activation, &index.to_string()) {
+            target.set_array_element(
+                target_start + i,
+                source.array_element(index),
+                activation.context.gc_context,
+            );
+        }
+    }
+}
+
 pub fn slice<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -300,28 +473,24 @@ pub fn slice<'gc>(
         .and_then(|v| v.coerce_to_f64(activation).ok())
         .map(|v| make_index_absolute(v as i32, this.length()))
         .unwrap_or(0);
+    // An undefined or NaN `end` slices to the end of the array.
     let end = args
         .get(1)
+        .filter(|v| !matches!(v, Value::Undefined))
         .and_then(|v| v.coerce_to_f64(activation).ok())
+        .filter(|v| !v.is_nan())
         .map(|v| make_index_absolute(v as i32, this.length()))
         .unwrap_or_else(|| this.length());
 
-    let array = ScriptObject::array(
+    let array = ScriptObject::array_with_capacity(
         activation.context.gc_context,
         Some(activation.context.avm1.prototypes.array),
+        end.saturating_sub(start),
     );
 
     if start < end {
-        let length = end - start;
-        array.set_length(activation.context.gc_context, length);
-
-        for i in 0..length {
-            array.set_array_element(
-                i,
-                this.array_element(start + i),
-                activation.context.gc_context,
-            );
-        }
+        array.set_length(activation.context.gc_context, end - start);
+        copy_array_elements(activation, this, start..end, array.into(), 0);
     }
 
     Ok(array.into())
@@ -413,36 +582,23 @@ pub fn concat<'gc>(
         activation.context.gc_context,
         Some(activation.context.avm1.prototypes.array),
     );
-    let mut length = 0;
-
-    for i in 0..this.length() {
-        let old = this
-            .get(&i.to_string(), activation)
-            .unwrap_or(Value::Undefined);
-        array.set_array_element(length, old, activation.context.gc_context);
-        length += 1;
-    }
+    let mut length = this.length();
+    copy_array_elements(activation, this, 0..length, array.into(), 0);
 
     for arg in args {
         let mut added = false;
 
         if let Value::Object(object) = arg {
             let object = *object;
-            if activation
-                .context
-                .avm1
-                .prototypes
-                .array
-                .is_prototype_of(object)
+            // Only genuine arrays are flattened; array-like objects are appended as-is.
+            if object
+                .as_script_object()
+                .map_or(false, |o| o.is_array_backed())
             {
                 added = true;
-                for i in 0..object.length() {
-                    let old = object
-                        .get(&i.to_string(), activation)
-                        .unwrap_or(Value::Undefined);
-                    array.set_array_element(length, old, activation.context.gc_context);
-                    length += 1;
-                }
+                let object_length = object.length();
+                copy_array_elements(activation, object, 0..object_length, array.into(), length);
+                length += object_length;
             }
         }
 
@@ -452,6 +608,8 @@ pub fn concat<'gc>(
         }
     }
 
+    // Trailing holes still count towards the length.
+    array.set_length(activation.context.gc_context, length);
     Ok(array.into())
 }
 
@@ -471,6 +629,8 @@ fn sort<'gc>(
     // Overloads:
     // 1) a.sort(flags: Number = 0): Sorts with the given flags.
     // 2) a.sort(compare_fn: Object, flags: Number = 0): Sorts using the given compare function and flags.
+    // When two numbers are given, the first occupies the compare function slot and is ignored,
+    // so `a.sort(x, flags)` always takes its flags from the second argument.
     use crate::ecma_conversions::f64_to_wrapping_i32;
     let (compare_fn, flags) = match args {
         [Value::Number(_), Value::Number(n), ..] => (None, f64_to_wrapping_i32(*n)),
@@ -490,13 +650,18 @@ fn sort<'gc>(
         sort_compare_string
     };
 
-    let compare_fn: CompareFn<'_, 'gc> = if let Some(f) = compare_fn {
-        let this = Value::Undefined.coerce_to_object(activation);
+    if let Some(f) = compare_fn {
         // this is undefined in the compare function
-        Box::new(move |activation, a: &Value<'gc>, b: &Value<'gc>| {
-            sort_compare_custom(activation, this, a, b, f)
-        })
-    } else if flags.contains(SortFlags::NUMERIC) {
+        let compare_this = Value::Undefined.coerce_to_object(activation);
+        return sort_with_function(
+            activation,
+            this,
+            |activation, a, b| sort_compare_custom(activation, compare_this, a, b, f),
+            flags,
+        );
+    }
+
+    let mut compare_fn: CompareFn<'_, 'gc> = if flags.contains(SortFlags::NUMERIC) {
         Box::new(sort_compare_numeric(
             flags.contains(SortFlags::CASE_INSENSITIVE),
         ))
@@ -504,7 +669,12 @@ fn sort<'gc>(
         Box::new(string_compare_fn)
     };
 
-    sort_with_function(activation, this, compare_fn, flags)
+    sort_with_function(
+        activation,
+        this,
+        |activation, a, b| Ok(compare_fn(activation, a, b)),
+        flags,
+    )
 }
 
 fn sort_on<'gc>(
@@ -564,6 +734,17 @@ fn sort_on<'gc>(
             .collect(),
     };
 
+    // A repeated field can't affect the order, so only its first occurrence and flags are kept.
+    let mut unique_fields = Vec::with_capacity(fields.len());
+    let mut unique_flags = Vec::with_capacity(flags.len());
+    for (field, flags) in fields.into_iter().zip(flags) {
+        if !unique_fields.contains(&field) {
+            unique_fields.push(field);
+            unique_flags.push(flags);
+        }
+    }
+    let (fields, flags) = (unique_fields, unique_flags);
+
     // CASEINSENSITIVE, UNIQUESORT, and RETURNINDEXEDARRAY are taken from the first set of flags in the array.
     let main_flags = flags[0];
 
@@ -587,15 +768,24 @@ fn sort_on<'gc>(
         })
         .collect();
 
-    let compare_fn = sort_compare_fields(fields, field_compare_fns);
+    let mut compare_fn = sort_compare_fields(fields, field_compare_fns);
 
-    sort_with_function(activation, this, compare_fn, main_flags)
+    sort_with_function(
+        activation,
+        this,
+        |activation, a, b| Ok(compare_fn(activation, a, b)),
+        main_flags,
+    )
 }
 
 fn sort_with_function<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
-    mut compare_fn: impl FnMut(&mut Activation<'_, 'gc, '_>, &Value<'gc>, &Value<'gc>) -> Ordering,
+    mut compare_fn: impl FnMut(
+        &mut Activation<'_, 'gc, '_>,
+        &Value<'gc>,
+        &Value<'gc>,
+    ) -> Result<Ordering, Error<'gc>>,
     flags: SortFlags,
 ) -> Result<Value<'gc>, Error<'gc>> {
     let length = this.length();
@@ -603,8 +793,20 @@ fn sort_with_function<'gc>(
     let array_proto = activation.context.avm1.prototypes.array;
 
     let mut is_unique = true;
-    values.sort_unstable_by(|a, b| {
-        let mut ret = compare_fn(activation, &a.1, &b.1);
+    let mut error = None;
+    // Flash's sort is stable, so elements comparing equal keep their original order.
+    values.sort_by(|a, b| {
+        // Once the compare function has failed, the remaining comparisons are skipped.
+        if error.is_some() {
+            return Ordering::Equal;
+        }
+        let mut ret = match compare_fn(activation, &a.1, &b.1) {
+            Ok(ret) => ret,
+            Err(e) => {
+                error = Some(e);
+                return Ordering::Equal;
+            }
+        };
         if flags.contains(SortFlags::DESCENDING) {
             ret = ret.reverse();
         }
@@ -614,6 +816,11 @@ fn sort_with_function<'gc>(
         ret
     });
 
+    // The original array is left untouched if the compare function threw.
+    if let Some(error) = error {
+        return Err(error);
+    }
+
     if flags.contains(SortFlags::UNIQUE_SORT) && !is_unique {
         // Check for uniqueness. Return 0 if there is a duplicated value.
         return Ok(0.into());
@@ -687,8 +894,12 @@ fn sort_compare_numeric<'gc>(
     case_insensitive: bool,
 ) -> impl FnMut(&mut Activation<'_, 'gc, '_>, &Value<'gc>, &Value<'gc>) -> Ordering {
     move |activation, a, b| {
-        if let (Value::Number(a), Value::Number(b)) = (a, b) {
-            a.partial_cmp(b).unwrap_or(DEFAULT_ORDERING)
+        // Both operands are coerced to numbers, so numeric strings compare by value.
+        // Only values that are both non-numeric are compared as strings.
+        let a_num = a.coerce_to_f64(activation).unwrap_or(f64::NAN);
+        let b_num = b.coerce_to_f64(activation).unwrap_or(f64::NAN);
+        if !a_num.is_nan() || !b_num.is_nan() {
+            a_num.partial_cmp(&b_num).unwrap_or(DEFAULT_ORDERING)
         } else if case_insensitive {
             sort_compare_string_ignore_case(activation, a, b)
         } else {
@@ -705,8 +916,13 @@ fn sort_compare_fields<'a, 'gc: 'a>(
         for (field_name, compare_fn) in field_names.iter().zip(compare_fns.iter_mut()) {
             let a_object = a.coerce_to_object(activation);
             let b_object = b.coerce_to_object(activation);
-            let a_prop = a_object.get(field_name, activation).unwrap();
-            let b_prop = b_object.get(field_name, activation).unwrap();
+            // A field that can't be read, such as one with a throwing getter, sorts as undefined.
+            let a_prop = a_object
+                .get(field_name, activation)
+                .unwrap_or(Value::Undefined);
+            let b_prop = b_object
+                .get(field_name, activation)
+                .unwrap_or(Value::Undefined);
 
             let result = compare_fn(activation, &a_prop, &b_prop);
             if result != Ordering::Equal {
@@ -725,16 +941,865 @@ fn sort_compare_custom<'gc>(
     a: &Value<'gc>,
     b: &Value<'gc>,
     compare_fn: &Value<'gc>,
-) -> Ordering {
-    // TODO: Handle errors.
+) -> Result<Ordering, Error<'gc>> {
     let args = [*a, *b];
+    // The result is coerced to a number, and NaN compares as equal.
     let ret = compare_fn
-        .call("[Compare]", activation, this, None, &args)
-        .unwrap_or(Value::Undefined);
-    match ret {
-        Value::Number(n) if n > 0.0 => Ordering::Greater,
-        Value::Number(n) if n < 0.0 => Ordering::Less,
-        Value::Number(n) if n == 0.0 => Ordering::Equal,
-        _ => DEFAULT_ORDERING,
+        .call("[Compare]", activation, this, None, &args)?
+        .coerce_to_f64(activation)?;
+    Ok(ret.partial_cmp(&0.0).unwrap_or(DEFAULT_ORDERING))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    fn new_array<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        values: &[Value<'gc>],
+    ) -> Object<'gc> {
+        let array = ScriptObject::array(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.array),
+        );
+        for (i, value) in values.iter().enumerate() {
+            array.set_array_element(i, *value, activation.context.gc_context);
+        }
+        array.into()
+    }
+
+    #[test]
+    fn push_wraps_length_to_uint32() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let object = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            object.set("length", 4294967295.0.into(), activation)?;
+            assert_eq!(object.length(), 0xFFFF_FFFF);
+
+            assert_eq!(push(activation, object.into(), &["a".into()])?, 0.into());
+            assert_eq!(object.length(), 0);
+            assert_eq!(object.get("length", activation)?, 0.into());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn push_call_on_object_with_length() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let object = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            object.set("length", 3.into(), activation)?;
+
+            // `Array.prototype.push.call(object, "x")`
+            let array_proto = activation.context.avm1.prototypes.array;
+            let push = array_proto
+                .get("push", activation)?
+                .coerce_to_object(activation);
+            let length = push.call("push", activation, object.into(), None, &["x".into()])?;
+            assert_eq!(length, 4.into());
+            assert_eq!(object.get("3", activation)?, "x".into());
+            assert_eq!(object.get("length", activation)?, 4.into());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn unshift_returns_new_length() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let this = new_array(activation, &[1.into(), 2.into()]);
+            assert_eq!(unshift(activation, this, &[0.into()])?, 3.into());
+            assert_eq!(this.get("length", activation)?, 3.into());
+            Ok(())
+        });
+    }
+
+    fn numbers<'gc>(values: &[f64]) -> Vec<Value<'gc>> {
+        values.iter().map(|n| Value::Number(*n)).collect()
+    }
+
+    fn ascending<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        FunctionObject::function(
+            activation.context.gc_context,
+            Executable::Native(|activation, _this, args| {
+                let a = args[0].coerce_to_f64(activation)?;
+                let b = args[1].coerce_to_f64(activation)?;
+                Ok((a - b).into())
+            }),
+            None,
+            activation.context.avm1.prototypes.function,
+        )
+    }
+
+    #[test]
+    fn sort_overloads() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let numeric = SortFlags::NUMERIC.bits().into();
+            let descending = SortFlags::DESCENDING.bits().into();
+
+            // sort(flags)
+            let this = new_array(activation, &numbers(&[10.0, 9.0, 100.0]));
+            sort(activation, this, &[numeric])?;
+            assert_eq!(this.array(), numbers(&[9.0, 10.0, 100.0]));
+
+            // sort(compare_fn)
+            let this = new_array(activation, &numbers(&[10.0, 9.0, 100.0]));
+            let compare_fn = ascending(activation);
+            sort(activation, this, &[compare_fn.into()])?;
+            assert_eq!(this.array(), numbers(&[9.0, 10.0, 100.0]));
+
+            // sort(compare_fn, flags)
+            let this = new_array(activation, &numbers(&[10.0, 9.0, 100.0]));
+            sort(activation, this, &[compare_fn.into(), descending])?;
+            assert_eq!(this.array(), numbers(&[100.0, 10.0, 9.0]));
+
+            // sort(flags, flags): the first number is ignored.
+            let this = new_array(activation, &numbers(&[10.0, 9.0, 100.0]));
+            sort(activation, this, &[descending, numeric])?;
+            assert_eq!(this.array(), numbers(&[9.0, 10.0, 100.0]));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn sort_numeric_coerces_strings() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let numeric = SortFlags::NUMERIC.bits().into();
+
+            let this = new_array(activation, &["10".into(), 2.into(), "1".into(), 30.into()]);
+            sort(activation, this, &[numeric])?;
+            let expected: Vec<Value> = vec!["1".into(), 2.into(), "10".into(), 30.into()];
+            assert_eq!(this.array(), expected);
+
+            // Non-numeric values still compare as strings against each other.
+            let this = new_array(activation, &["b".into(), "a".into(), "c".into()]);
+            sort(activation, this, &[numeric])?;
+            let expected: Vec<Value> = vec!["a".into(), "b".into(), "c".into()];
+            assert_eq!(this.array(), expected);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn sort_on_ignores_duplicate_fields() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let mut elements: Vec<Value> = vec![];
+            for (a, b) in &[(2, 1), (1, 2), (2, 3), (1, 1)] {
+                let element = ScriptObject::object(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes.object),
+                );
+                element.set("a", (*a).into(), activation)?;
+                element.set("b", (*b).into(), activation)?;
+                elements.push(element.into());
+            }
+            let numeric = SortFlags::NUMERIC.bits().into();
+            let descending = (SortFlags::NUMERIC | SortFlags::DESCENDING).bits().into();
+
+            let expected = new_array(activation, &elements);
+            let fields = new_array(activation, &["a".into(), "b".into()]);
+            let flags = new_array(activation, &[numeric, descending]);
+            sort_on(activation, expected, &[fields.into(), flags.into()])?;
+
+            let this = new_array(activation, &elements);
+            let fields = new_array(activation, &["a".into(), "a".into(), "b".into()]);
+            let flags = new_array(activation, &[numeric, numeric, descending]);
+            sort_on(activation, this, &[fields.into(), flags.into()])?;
+            assert_eq!(this.array(), expected.array());
+
+            // The duplicate's flags are ignored in favor of the first occurrence.
+            let this = new_array(activation, &elements);
+            let fields = new_array(activation, &["a".into(), "a".into(), "b".into()]);
+            let flags = new_array(activation, &[numeric, descending, descending]);
+            sort_on(activation, this, &[fields.into(), flags.into()])?;
+            assert_eq!(this.array(), expected.array());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn sort_is_stable_with_compare_function() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let mut elements: Vec<Value> = vec![];
+            for (k, id) in &[(1, "a"), (0, "z"), (1, "b"), (1, "c")] {
+                let element = ScriptObject::object(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes.object),
+                );
+                element.set("k", (*k).into(), activation)?;
+                element.set("id", (*id).into(), activation)?;
+                elements.push(element.into());
+            }
+            let this = new_array(activation, &elements);
+
+            let compare_fn = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, _this, args| {
+                    let a = args[0].coerce_to_object(activation).get("k", activation)?;
+                    let b = args[1].coerce_to_object(activation).get("k", activation)?;
+                    Ok((a.coerce_to_f64(activation)? - b.coerce_to_f64(activation)?).into())
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            sort(activation, this, &[compare_fn.into()])?;
+
+            let mut ids = vec![];
+            for element in this.array() {
+                let id = element.coerce_to_object(activation).get("id", activation)?;
+                ids.push(id.coerce_to_string(activation)?.to_string());
+            }
+            assert_eq!(ids, vec!["z", "a", "b", "c"]);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn sort_indexed_with_compare_function() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let elements = numbers(&[3.0, 1.0, 3.0, 2.0]);
+            let this = new_array(activation, &elements);
+            let descending = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, _this, args| {
+                    let a = args[0].coerce_to_f64(activation)?;
+                    let b = args[1].coerce_to_f64(activation)?;
+                    Ok((b - a).into())
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let flags = SortFlags::RETURN_INDEXED_ARRAY.bits().into();
+
+            let indices =
+                sort(activation, this, &[descending.into(), flags])?.coerce_to_object(activation);
+            assert_eq!(this.array(), elements);
+            // Equal elements keep their original order.
+            assert_eq!(indices.array(), numbers(&[0.0, 2.0, 3.0, 1.0]));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn sort_keeps_original_when_compare_function_throws() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let elements = numbers(&[3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0]);
+            let this = new_array(activation, &elements);
+            // Throws on the third comparison, counting calls on its `this` object.
+            let throw_on_third = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, this, args| {
+                    let calls = this.get("calls", activation)?.coerce_to_i32(activation)? + 1;
+                    this.set("calls", calls.into(), activation)?;
+                    if calls == 3 {
+                        return Err(Error::ThrownValue("boom".into()));
+                    }
+                    let a = args[0].coerce_to_f64(activation)?;
+                    let b = args[1].coerce_to_f64(activation)?;
+                    Ok((a - b).into())
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+
+            let result = sort(activation, this, &[throw_on_third.into()]);
+            assert!(matches!(result, Err(Error::ThrownValue(value)) if value == "boom".into()));
+            assert_eq!(this.array(), elements);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn slice_and_concat_preserve_holes() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let this = new_array(activation, &numbers(&[1.0, 2.0, 3.0, 4.0]));
+            this.delete(activation, "1");
+            this.delete(activation, "3");
+
+            let sliced = slice(activation, this, &[])?.coerce_to_object(activation);
+            let concatenated =
+                concat(activation, this, &[this.into()])?.coerce_to_object(activation);
+
+            assert_eq!(sliced.length(), 4);
+            assert_eq!(sliced.own_enumerable_keys(activation), vec!["0", "2"]);
+            assert_eq!(concatenated.length(), 8);
+            assert_eq!(
+                concatenated.own_enumerable_keys(activation),
+                vec!["0", "2", "4", "6"]
+            );
+            assert_eq!(sliced.array_element(1), Value::Undefined);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn sort_on_primitives() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let elements = numbers(&[3.0, 1.0, 2.0]);
+            let this = new_array(activation, &elements);
+            // Primitives have no fields, so every element compares equal and keeps its place.
+            let result = sort_on(activation, this, &["x".into()])?;
+            assert_eq!(result, this.into());
+            assert_eq!(this.array(), elements);
+
+            let this = new_array(activation, &numbers(&[1.0, 2.0, 3.0]));
+            sort_on(activation, this, &["x".into()])?;
+            assert_eq!(this.array(), numbers(&[1.0, 2.0, 3.0]));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn sort_coerces_compare_result() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            // Returns the difference of its arguments as a string.
+            let string_difference = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, _this, args| {
+                    let a = args[0].coerce_to_f64(activation)?;
+                    let b = args[1].coerce_to_f64(activation)?;
+                    let difference =
+                        AvmString::new(activation.context.gc_context, (a - b).to_string());
+                    Ok(difference.into())
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let this = new_array(activation, &numbers(&[3.0, 10.0, 1.0, 2.0]));
+            sort(activation, this, &[string_difference.into()])?;
+            assert_eq!(this.array(), numbers(&[1.0, 2.0, 3.0, 10.0]));
+
+            // Returns its first argument.
+            let identity = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, args| Ok(args[0])),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let compare_this = Value::Undefined.coerce_to_object(activation);
+            let cases: [(Value<'_>, Ordering); 6] = [
+                (true.into(), Ordering::Greater),
+                (false.into(), Ordering::Equal),
+                ("1".into(), Ordering::Greater),
+                ("-1".into(), Ordering::Less),
+                ("abc".into(), Ordering::Equal),
+                (Value::Undefined, Ordering::Equal),
+            ];
+            for (result, expected) in cases.iter() {
+                let ordering = sort_compare_custom(
+                    activation,
+                    compare_this,
+                    result,
+                    &Value::Undefined,
+                    &identity.into(),
+                )?;
+                assert_eq!(ordering, *expected, "{:?}", result);
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn reverse_preserves_holes() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            // A sparse, properties-backed array.
+            let object = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            object.set("0", "a".into(), activation)?;
+            object.set("3", "d".into(), activation)?;
+            object.set_length(activation.context.gc_context, 5);
+            reverse(activation, object.into(), &[])?;
+            assert_eq!(object.get("1", activation)?, "d".into());
+            assert_eq!(object.get("4", activation)?, "a".into());
+            for i in &["0", "2", "3"] {
+                assert!(!object.has_own_property(activation, i), "{}", i);
+            }
+            assert_eq!(object.length(), 5);
+
+            let this = new_array(activation, &numbers(&[1.0, 2.0, 3.0, 4.0]));
+            this.delete(activation, "1");
+            reverse(activation, this, &[])?;
+            assert_eq!(
+                this.array(),
+                vec![
+                    Value::from(4),
+                    Value::from(3),
+                    Value::Undefined,
+                    Value::from(1)
+                ]
+            );
+            assert!(!this.has_own_property(activation, "2"));
+            assert!(this.has_own_property(activation, "1"));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn concat_flattens_array_subclass() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let array_proto = activation.context.avm1.prototypes.array;
+            let sub_proto = array_proto.create_bare_object(activation, array_proto)?;
+            let sub = sub_proto.create_bare_object(activation, sub_proto)?;
+            sub.set_array_element(0, 2.into(), activation.context.gc_context);
+            sub.set_array_element(1, 3.into(), activation.context.gc_context);
+
+            let this = new_array(activation, &[1.into()]);
+            let result = concat(activation, this, &[sub.into()])?.coerce_to_object(activation);
+            assert_eq!(
+                result.array(),
+                vec![Value::Number(1.0), Value::Number(2.0), Value::Number(3.0)]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn concat_does_not_flatten_array_like() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let array_like = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            array_like.set("length", 2.into(), activation)?;
+            array_like.set("0", "a".into(), activation)?;
+            array_like.set("1", "b".into(), activation)?;
+
+            let inherits = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            );
+
+            let this = new_array(activation, &[1.into()]);
+            let result = concat(activation, this, &[array_like.into(), inherits.into()])?
+                .coerce_to_object(activation);
+            assert_eq!(result.length(), 3);
+            assert_eq!(result.array_element(1), Value::Object(array_like.into()));
+            assert_eq!(result.array_element(2), Value::Object(inherits.into()));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn reverse_and_join_large_array() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            const LENGTH: usize = 50_000;
+            let values: Vec<Value> = (0..LENGTH).map(|i| Value::Number(i as f64)).collect();
+            let this = new_array(activation, &values);
+
+            reverse(activation, this, &[])?;
+            assert_eq!(this.array_element(0), Value::Number((LENGTH - 1) as f64));
+            assert_eq!(this.array_element(LENGTH - 1), Value::Number(0.0));
+
+            let joined = join(activation, this, &[])?.coerce_to_string(activation)?;
+            assert!(joined.starts_with("49999,49998,"));
+            assert!(joined.ends_with(",1,0"));
+            Ok(())
+        });
+    }
+
+    /// A callback returning whether its element is greater than 2, counting its calls on `this`.
+    fn greater_than_two<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        FunctionObject::function(
+            activation.context.gc_context,
+            Executable::Native(|activation, this, args| {
+                let calls = this.get("calls", activation)?.coerce_to_f64(activation)?;
+                this.set("calls", (calls + 1.0).into(), activation)?;
+                Ok((args[0].coerce_to_f64(activation)? > 2.0).into())
+            }),
+            None,
+            activation.context.avm1.prototypes.function,
+        )
+    }
+
+    #[test]
+    fn some_short_circuits() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let callback = greater_than_two(activation);
+            let counter = ScriptObject::object(activation.context.gc_context, None);
+
+            let this = new_array(activation, &numbers(&[1.0, 3.0, 5.0, 7.0]));
+            counter.set("calls", 0.into(), activation)?;
+            assert_eq!(
+                some(activation, this, &[callback.into(), counter.into()])?,
+                true.into()
+            );
+            assert_eq!(counter.get("calls", activation)?, 2.into());
+
+            let this = new_array(activation, &numbers(&[1.0, 2.0]));
+            counter.set("calls", 0.into(), activation)?;
+            assert_eq!(
+                some(activation, this, &[callback.into(), counter.into()])?,
+                false.into()
+            );
+            assert_eq!(counter.get("calls", activation)?, 2.into());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn some_and_every_bind_this_arg() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            // Stores its receiver on itself, then keeps iterating.
+            let callback = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, this, _args| {
+                    this.set("receiver", this.into(), activation)?;
+                    Ok(Value::Undefined)
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let this = new_array(activation, &numbers(&[1.0, 2.0]));
+
+            for method in &[some, every] {
+                let this_arg = ScriptObject::object(activation.context.gc_context, None);
+                method(activation, this, &[callback.into(), this_arg.into()])?;
+                assert_eq!(
+                    this_arg.get("receiver", activation)?,
+                    Value::Object(this_arg.into())
+                );
+
+                // Without a `thisArg`, the callback still runs.
+                method(activation, this, &[callback.into()])?;
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn every_short_circuits() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let callback = greater_than_two(activation);
+            let counter = ScriptObject::object(activation.context.gc_context, None);
+
+            let this = new_array(activation, &numbers(&[3.0, 1.0, 5.0, 7.0]));
+            counter.set("calls", 0.into(), activation)?;
+            assert_eq!(
+                every(activation, this, &[callback.into(), counter.into()])?,
+                false.into()
+            );
+            assert_eq!(counter.get("calls", activation)?, 2.into());
+
+            let this = new_array(activation, &numbers(&[3.0, 4.0]));
+            counter.set("calls", 0.into(), activation)?;
+            assert_eq!(
+                every(activation, this, &[callback.into(), counter.into()])?,
+                true.into()
+            );
+            assert_eq!(counter.get("calls", activation)?, 2.into());
+
+            // Vacuously true for an empty array.
+            let this = new_array(activation, &[]);
+            assert_eq!(every(activation, this, &[callback.into()])?, true.into());
+            Ok(())
+        });
+    }
+
+    /// A callback appending `element` to `accumulator` as a string.
+    fn append_element<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        FunctionObject::function(
+            activation.context.gc_context,
+            Executable::Native(|activation, _this, args| {
+                let accumulator = args[0].coerce_to_string(activation)?;
+                let element = args[1].coerce_to_string(activation)?;
+                Ok(AvmString::new(
+                    activation.context.gc_context,
+                    format!("{}{}", accumulator, element),
+                )
+                .into())
+            }),
+            None,
+            activation.context.avm1.prototypes.function,
+        )
+    }
+
+    #[test]
+    fn reduce_sums_elements() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let add = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, _this, args| {
+                    Ok(
+                        (args[0].coerce_to_f64(activation)? + args[1].coerce_to_f64(activation)?)
+                            .into(),
+                    )
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let this = new_array(activation, &numbers(&[1.0, 2.0, 3.0, 4.0]));
+            assert_eq!(reduce(activation, this, &[add.into()])?, 10.into());
+            assert_eq!(
+                reduce(activation, this, &[add.into(), 5.into()])?,
+                15.into()
+            );
+
+            // A single element with no initial value is returned without calling back.
+            let this = new_array(activation, &numbers(&[7.0]));
+            assert_eq!(reduce(activation, this, &[add.into()])?, 7.into());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn reduce_empty_array_without_initial_value_throws() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let callback = append_element(activation);
+            let this = new_array(activation, &[]);
+            assert!(matches!(
+                reduce(activation, this, &[callback.into()]),
+                Err(Error::ThrownValue(_))
+            ));
+            assert!(matches!(
+                reduce_right(activation, this, &[callback.into()]),
+                Err(Error::ThrownValue(_))
+            ));
+            assert_eq!(
+                reduce(activation, this, &[callback.into(), "seed".into()])?,
+                "seed".into()
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn reduce_right_visits_elements_in_reverse() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let callback = append_element(activation);
+            let this = new_array(activation, &["a".into(), "b".into(), "c".into()]);
+            assert_eq!(
+                reduce_right(activation, this, &[callback.into()])?,
+                "cba".into()
+            );
+            assert_eq!(
+                reduce_right(activation, this, &[callback.into(), ">".into()])?,
+                ">cba".into()
+            );
+            assert_eq!(reduce(activation, this, &[callback.into()])?, "abc".into());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn reduce_skips_holes() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let callback = append_element(activation);
+            let this = new_array(activation, &["a".into(), "b".into(), "c".into()]);
+            this.delete(activation, "0");
+            this.delete(activation, "2");
+            assert_eq!(this.length(), 3);
+            assert_eq!(reduce(activation, this, &[callback.into()])?, "b".into());
+            assert_eq!(
+                reduce_right(activation, this, &[callback.into(), ">".into()])?,
+                ">b".into()
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn some_propagates_errors() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let callback = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, args| Err(Error::ThrownValue(args[0]))),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let this = new_array(activation, &numbers(&[1.0]));
+            assert!(matches!(
+                some(activation, this, &[callback.into()]),
+                Err(Error::ThrownValue(Value::Number(n))) if n == 1.0
+            ));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn join_nested_arrays() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let inner = new_array(activation, &numbers(&[2.0, 3.0]));
+            let this = new_array(activation, &[1.into(), inner.into()]);
+            assert_eq!(join(activation, this, &[])?, "1,2,3".into());
+            // The separator only applies to the outer array.
+            assert_eq!(join(activation, this, &["-".into()])?, "1-2,3".into());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn push_many_elements() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            const COUNT: usize = 50_000;
+            let this = new_array(activation, &numbers(&[0.0]));
+            for i in 1..COUNT {
+                push(activation, this, &[i.into()])?;
+            }
+            let values: Vec<f64> = (0..COUNT).map(|i| i as f64).collect();
+            let elements = numbers(&values);
+            assert_eq!(this.length(), COUNT);
+            assert_eq!(this.get("length", activation)?, COUNT.into());
+            assert_eq!(this.array(), elements);
+
+            // Pushing several elements at once stores each of them.
+            let this = new_array(activation, &[]);
+            assert_eq!(push(activation, this, &elements)?, COUNT.into());
+            assert_eq!(this.get("length", activation)?, COUNT.into());
+            assert_eq!(this.get("49999", activation)?, 49999.into());
+            assert_eq!(this.array(), elements);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn join_cyclic_array() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let this = new_array(activation, &[1.into()]);
+            push(activation, this, &[this.into()])?;
+            assert_eq!(to_string(activation, this, &[])?, "1,".into());
+
+            let outer = new_array(activation, &[2.into(), this.into()]);
+            this.set_array_element(1, outer.into(), activation.context.gc_context);
+            assert_eq!(to_string(activation, outer, &[])?, "2,1,".into());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn join_separators() {
+        for &version in &[6, 19] {
+            with_avm(version, |activation, _this| -> Result<(), Error> {
+                let this = new_array(activation, &numbers(&[1.0, 2.0, 3.0]));
+                assert_eq!(join(activation, this, &["".into()])?, "123".into());
+                assert_eq!(join(activation, this, &[])?, "1,2,3".into());
+                assert_eq!(join(activation, this, &[Value::Undefined])?, "1,2,3".into());
+                Ok(())
+            });
+        }
+    }
+
+    #[test]
+    fn join_null_and_undefined() {
+        for &version in &[6, 7] {
+            with_avm(version, |activation, _this| -> Result<(), Error> {
+                let this = new_array(
+                    activation,
+                    &[1.into(), Value::Undefined, Value::Null, 2.into()],
+                );
+                assert_eq!(join(activation, this, &[])?, "1,,,2".into());
+                Ok(())
+            });
+        }
+    }
+
+    #[test]
+    fn concat_matches_elements() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let this = new_array(activation, &numbers(&[1.0, 2.0]));
+            let other = new_array(activation, &numbers(&[3.0, 4.0]));
+            let result =
+                concat(activation, this, &[other.into(), 5.into()])?.coerce_to_object(activation);
+            assert_eq!(result.array(), numbers(&[1.0, 2.0, 3.0, 4.0, 5.0]));
+            assert_eq!(result.length(), 5);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn construct_with_elements() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let elements = numbers(&[1.0, 2.0, 3.0]);
+            let this: Object<'_> = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            )
+            .into();
+            let this = constructor(activation, this, &elements)?.coerce_to_object(activation);
+            assert_eq!(this.length(), 3);
+            assert_eq!(this.get("length", activation)?, 3.into());
+            assert_eq!(this.array(), elements);
+            assert_eq!(this.get("2", activation)?, 3.into());
+
+            let array = array_function(activation, this, &elements)?.coerce_to_object(activation);
+            assert_eq!(array.length(), 3);
+            assert_eq!(array.get("length", activation)?, 3.into());
+            assert_eq!(array.array(), elements);
+
+            let array = array_function(activation, this, &[5.into()])?.coerce_to_object(activation);
+            assert_eq!(array.length(), 5);
+            assert_eq!(array.array_element(0), Value::Undefined);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn slice_undefined_or_nan_end() {
+        for &version in &[6, 19] {
+            with_avm(version, |activation, _this| -> Result<(), Error> {
+                let array = new_array(activation, &numbers(&[1.0, 2.0, 3.0, 4.0]));
+                for &end in &[Value::Undefined, f64::NAN.into()] {
+                    let sliced =
+                        slice(activation, array, &[1.into(), end])?.coerce_to_object(activation);
+                    assert_eq!(sliced.array(), numbers(&[2.0, 3.0, 4.0]));
+                }
+
+                let sliced = slice(activation, array, &[1.into(), (-1).into()])?
+                    .coerce_to_object(activation);
+                assert_eq!(sliced.array(), numbers(&[2.0, 3.0]));
+                Ok(())
+            });
+        }
+    }
+
+    #[test]
+    fn construct_on_plain_object() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let object: Object<'_> = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            )
+            .into();
+            constructor(activation, object, &[])?;
+            assert_eq!(object.length(), 0);
+            assert_eq!(object.get("length", activation)?, 0.into());
+
+            assert_eq!(push(activation, object, &numbers(&[1.0, 2.0]))?, 2.into());
+            assert_eq!(push(activation, object, &[3.into()])?, 3.into());
+            assert_eq!(object.array(), numbers(&[1.0, 2.0, 3.0]));
+            assert_eq!(object.get("2", activation)?, 3.into());
+
+            assert_eq!(pop(activation, object, &[])?, 3.into());
+            assert_eq!(object.length(), 2);
+            assert_eq!(object.get("length", activation)?, 2.into());
+            assert_eq!(object.get("2", activation)?, Value::Undefined);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn construct_with_fractional_or_negative_length() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let array_proto = activation.context.avm1.prototypes.array;
+            let cases = [(2.9, 2), (0.5, 0), (-1.0, 0), (-2.5, 0)];
+            for &(length, expected) in &cases {
+                // `new Array(length)` truncates toward zero, and negative lengths give an empty array.
+                let this = array_proto.create_bare_object(activation, array_proto)?;
+                constructor(activation, this, &[length.into()])?;
+                assert_eq!(this.length(), expected, "new Array({})", length);
+                assert_eq!(this.get("length", activation)?, expected.into());
+
+                // `Array(length)` behaves the same.
+                let array = array_function(activation, this, &[length.into()])?
+                    .coerce_to_object(activation);
+                assert_eq!(array.length(), expected, "Array({})", length);
+            }
+            Ok(())
+        });
     }
 }
@@ -5,7 +5,7 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::Object;
 use crate::avm1::{ScriptObject, Value};
 use crate::context_menu;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{StageQuality, TDisplayObject};
 use gc_arena::MutationContext;
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
@@ -152,12 +152,15 @@ pub fn create_proto<'gc>(
 
 pub fn make_context_menu_state<'gc>(
     menu: Option<Object<'gc>>,
+    show_builtin_items: bool,
     activation: &mut Activation<'_, 'gc, '_>,
 ) -> context_menu::ContextMenuState<'gc> {
     let mut result = context_menu::ContextMenuState::new();
 
     let root_mc = activation.context.stage.root_clip().as_movie_clip();
-    let builtin_items = {
+    let builtin_items = if !show_builtin_items {
+        vec![]
+    } else {
         let is_multiframe_movie = root_mc.map(|mc| mc.total_frames() > 1).unwrap_or(false);
         let mut names = if is_multiframe_movie {
             vec![
@@ -182,6 +185,18 @@ pub fn make_context_menu_state<'gc>(
         names
     };
 
+    if builtin_items.contains(&"quality") {
+        let is_high_quality = activation.context.stage.quality() != StageQuality::Low;
+        result.push(
+            context_menu::ContextMenuItem {
+                enabled: true,
+                separator_before: true,
+                caption: "High Quality".to_string(),
+                checked: is_high_quality,
+            },
+            context_menu::ContextMenuCallback::Quality,
+        );
+    }
     if builtin_items.contains(&"play") {
         let is_playing_root_movie = root_mc.unwrap().playing();
         result.push(
@@ -14,6 +14,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "addProperty" => method(add_property; DONT_ENUM | DONT_DELETE);
     "hasOwnProperty" => method(has_own_property; DONT_ENUM | DONT_DELETE);
     "isPropertyEnumerable" => method(is_property_enumerable; DONT_DELETE | DONT_ENUM);
+    "propertyIsEnumerable" => method(is_property_enumerable; DONT_DELETE | DONT_ENUM);
     "isPrototypeOf" => method(is_prototype_of; DONT_ENUM | DONT_DELETE);
     "toString" => method(to_string; DONT_ENUM | DONT_DELETE);
     "valueOf" => method(value_of; DONT_ENUM | DONT_DELETE);
@@ -68,14 +69,14 @@ pub fn add_property<'gc>(
 
     match getter {
         Value::Object(get) if !name.is_empty() => {
-            if let Value::Object(set) = setter {
+            let added = if let Value::Object(set) = setter {
                 this.add_property_with_case(
                     activation,
                     &name,
                     get.to_owned(),
                     Some(set.to_owned()),
                     Attribute::empty(),
-                );
+                )
             } else if let Value::Null = setter {
                 this.add_property_with_case(
                     activation,
@@ -83,12 +84,12 @@ pub fn add_property<'gc>(
                     get.to_owned(),
                     None,
                     Attribute::READ_ONLY,
-                );
+                )
             } else {
-                return Ok(false.into());
-            }
+                false
+            };
 
-            Ok(true.into())
+            Ok(added.into())
         }
         _ => Ok(false.into()),
     }
@@ -117,7 +118,9 @@ fn to_string<'gc>(
     Ok("[object Object]".into())
 }
 
-/// Implements `Object.prototype.isPropertyEnumerable`
+/// Implements `Object.prototype.isPropertyEnumerable` and `propertyIsEnumerable`
+///
+/// Only own properties are considered; inherited properties are never enumerable.
 fn is_property_enumerable<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -315,14 +318,13 @@ pub fn as_set_prop_flags<'gc>(
 
     match properties {
         Some(properties) => {
-            for prop_name in properties {
-                object.set_attributes(
-                    activation.context.gc_context,
-                    Some(&prop_name),
-                    set_attributes,
-                    clear_attributes,
-                )
-            }
+            let names: Vec<&str> = properties.iter().map(String::as_str).collect();
+            object.set_attributes_list(
+                activation.context.gc_context,
+                &names,
+                set_attributes,
+                clear_attributes,
+            )
         }
         None => object.set_attributes(
             activation.context.gc_context,
@@ -351,3 +353,232 @@ pub fn create_object_object<'gc>(
     define_properties_on(OBJECT_DECLS, gc_context, object, fn_proto);
     object_function
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    #[test]
+    fn property_is_enumerable() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let proto = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            proto.set("inherited", 1.into(), activation)?;
+            let object = ScriptObject::object(activation.context.gc_context, Some(proto.into()));
+            object.set("own", 2.into(), activation)?;
+            object.define_value(
+                activation.context.gc_context,
+                "hidden",
+                3.into(),
+                Attribute::DONT_ENUM,
+            );
+
+            for method in &["propertyIsEnumerable", "isPropertyEnumerable"] {
+                for (name, expected) in &[("own", true), ("hidden", false), ("inherited", false)] {
+                    assert_eq!(
+                        object.call_method(method, &[(*name).into()], activation)?,
+                        (*expected).into()
+                    );
+                }
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn has_own_property_case_sensitivity() {
+        for &(swf_version, case_insensitive_match) in &[(5, true), (6, true), (7, false)] {
+            with_avm(swf_version, |activation, _this| -> Result<(), Error> {
+                let object = ScriptObject::object(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes.object),
+                );
+                object.set("foo", 1.into(), activation)?;
+
+                assert_eq!(
+                    object.call_method("hasOwnProperty", &["foo".into()], activation)?,
+                    true.into()
+                );
+                assert_eq!(
+                    object.call_method("hasOwnProperty", &["Foo".into()], activation)?,
+                    case_insensitive_match.into(),
+                    "SWFv{}",
+                    swf_version
+                );
+                Ok(())
+            });
+        }
+    }
+
+    #[test]
+    fn as_set_prop_flags_with_name_list() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let object = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            object.set("a", 1.into(), activation)?;
+            object.set("b", 2.into(), activation)?;
+            object.set("c", 3.into(), activation)?;
+
+            let names = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            );
+            names.set_array_element(0, "a".into(), activation.context.gc_context);
+            names.set_array_element(1, "c".into(), activation.context.gc_context);
+
+            as_set_prop_flags(
+                activation,
+                object.into(),
+                &[
+                    object.into(),
+                    names.into(),
+                    Attribute::DONT_ENUM.bits().into(),
+                ],
+            )?;
+
+            assert_eq!(object.get_keys(activation), vec!["b"]);
+            assert!(!object.is_property_enumerable(activation, "a"));
+            assert!(!object.is_property_enumerable(activation, "c"));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn to_string_defaults_and_overrides() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let object = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            assert_eq!(
+                Value::from(object).coerce_to_string(activation)?,
+                "[object Object]"
+            );
+
+            let array = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            );
+            array.set_array_element(0, 1.into(), activation.context.gc_context);
+            array.set_array_element(1, "a".into(), activation.context.gc_context);
+            assert_eq!(Value::from(array).coerce_to_string(activation)?, "1,a");
+
+            let custom = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok("custom".into())),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            object.set("toString", custom.into(), activation)?;
+            assert_eq!(Value::from(object).coerce_to_string(activation)?, "custom");
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn add_property_result() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let object = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            let getter = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok("virtual".into())),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let other_getter = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok("replaced".into())),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+
+            // A fresh property is added.
+            assert_eq!(
+                add_property(
+                    activation,
+                    object.into(),
+                    &["prop".into(), getter.into(), Value::Null]
+                )?,
+                true.into()
+            );
+            assert_eq!(object.get("prop", activation)?, "virtual".into());
+
+            // An existing virtual property is replaced.
+            assert_eq!(
+                add_property(
+                    activation,
+                    object.into(),
+                    &["prop".into(), other_getter.into(), Value::Null]
+                )?,
+                true.into()
+            );
+            assert_eq!(object.get("prop", activation)?, "replaced".into());
+
+            // An undeletable stored value is kept.
+            object.define_value(
+                activation.context.gc_context,
+                "stored",
+                "stored".into(),
+                Attribute::DONT_DELETE,
+            );
+            assert_eq!(
+                add_property(
+                    activation,
+                    object.into(),
+                    &["stored".into(), getter.into(), Value::Null]
+                )?,
+                false.into()
+            );
+            assert_eq!(object.get("stored", activation)?, "stored".into());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn add_property_without_setter() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let object = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            let getter = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok("read-only".into())),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+
+            // A null setter makes a getter-only property, which ignores writes.
+            assert_eq!(
+                add_property(
+                    activation,
+                    object.into(),
+                    &["prop".into(), getter.into(), Value::Null]
+                )?,
+                true.into()
+            );
+            object.set("prop", "written".into(), activation)?;
+            assert_eq!(object.get("prop", activation)?, "read-only".into());
+
+            // An undefined setter is rejected, as in Flash.
+            assert_eq!(
+                add_property(
+                    activation,
+                    object.into(),
+                    &["other".into(), getter.into(), Value::Undefined]
+                )?,
+                false.into()
+            );
+            assert!(!object.has_own_property(activation, "other"));
+            Ok(())
+        });
+    }
+}
@@ -10,6 +10,7 @@ use gc_arena::MutationContext;
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "align" => property(align, set_align; DONT_ENUM | DONT_DELETE);
+    "displayState" => property(display_state, set_display_state; DONT_ENUM | DONT_DELETE);
     "height" => property(height; DONT_ENUM | DONT_DELETE | READ_ONLY);
     "scaleMode" => property(scale_mode, set_scale_mode; DONT_ENUM | DONT_DELETE);
     "showMenu" => property(show_menu, set_show_menu; DONT_ENUM | DONT_DELETE);
@@ -75,6 +76,39 @@ fn set_align<'gc>(
     Ok(Value::Undefined)
 }
 
+fn display_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let stage = activation.context.stage;
+    stage.sync_display_state(&mut activation.context);
+    let display_state = AvmString::new(
+        activation.context.gc_context,
+        activation.context.stage.display_state().to_string(),
+    );
+    Ok(display_state.into())
+}
+
+fn set_display_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Ok(display_state) = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .parse()
+    {
+        activation
+            .context
+            .stage
+            .set_display_state(&mut activation.context, display_state);
+    }
+    Ok(Value::Undefined)
+}
+
 fn height<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
@@ -100,16 +134,18 @@ fn set_scale_mode<'gc>(
     _this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let scale_mode = args
+    // Invalid scale modes are ignored.
+    if let Ok(scale_mode) = args
         .get(0)
         .unwrap_or(&Value::Undefined)
         .coerce_to_string(activation)?
         .parse()
-        .unwrap_or_default();
-    activation
-        .context
-        .stage
-        .set_scale_mode(&mut activation.context, scale_mode);
+    {
+        activation
+            .context
+            .stage
+            .set_scale_mode(&mut activation.context, scale_mode);
+    }
     Ok(Value::Undefined)
 }
 
@@ -145,3 +181,23 @@ fn width<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     Ok(activation.context.stage.stage_size().0.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    #[test]
+    fn scale_mode_ignores_invalid_values() {
+        with_avm(19, |activation, this| -> Result<(), Error> {
+            assert_eq!(scale_mode(activation, this, &[])?, "showAll".into());
+
+            set_scale_mode(activation, this, &["noScale".into()])?;
+            assert_eq!(scale_mode(activation, this, &[])?, "noScale".into());
+
+            set_scale_mode(activation, this, &["bogus".into()])?;
+            assert_eq!(scale_mode(activation, this, &[])?, "noScale".into());
+            Ok(())
+        });
+    }
+}
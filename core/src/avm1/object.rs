@@ -2,6 +2,7 @@
 
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, ExecutionReason, FunctionObject};
+use crate::avm1::object::script_object::Watcher;
 use crate::avm1::object::shared_object::SharedObject;
 use crate::avm1::object::super_object::SuperObject;
 use crate::avm1::object::value_object::ValueObject;
@@ -24,7 +25,7 @@ use crate::avm1::object::transform_object::TransformObject;
 use crate::avm1::object::xml_attributes_object::XmlAttributesObject;
 use crate::avm1::object::xml_idmap_object::XmlIdMapObject;
 use crate::avm1::object::xml_object::XmlObject;
-use crate::avm1::{ScriptObject, SoundObject, StageObject, Value};
+use crate::avm1::{AvmString, ScriptObject, SoundObject, StageObject, Value};
 use crate::avm_warn;
 use crate::display_object::DisplayObject;
 use crate::xml::XmlNode;
@@ -111,12 +112,28 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: &str,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Value<'gc>, Error<'gc>> {
+        let this = (*self).into();
         if name == "__proto__" {
-            return Ok(self.proto());
+            // A stored `__proto__` property takes precedence over the real prototype.
+            return match self.get_local(name, activation, this) {
+                Some(value) => value,
+                None => Ok(self.proto()),
+            };
         }
 
-        let this = (*self).into();
-        Ok(search_prototype(Value::Object(this), name, activation, this)?.0)
+        let (value, found) = search_prototype(Value::Object(this), name, activation, this)?;
+        if found.is_some() || name == "__resolve" {
+            return Ok(value);
+        }
+
+        // Missing properties are routed through a `__resolve` method, if one is defined.
+        let resolve = search_prototype(Value::Object(this), "__resolve", activation, this)?.0;
+        if let Value::Object(_) = resolve {
+            let name = AvmString::new(activation.context.gc_context, name.to_string());
+            resolve.call("__resolve", activation, this, None, &[name.into()])
+        } else {
+            Ok(Value::Undefined)
+        }
     }
 
     fn set_local(
@@ -140,28 +157,28 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         }
 
         if name == "__proto__" {
+            // As in Flash, an assignment that would create a prototype cycle is ignored.
+            if let Value::Object(proto) = value {
+                if proto.as_ptr() == self.as_ptr() || self.is_prototype_of(proto) {
+                    return Ok(());
+                }
+            }
             self.set_proto(activation.context.gc_context, value);
             return Ok(());
         }
 
-        if let Ok(index) = name.parse::<usize>() {
-            self.set_array_element(index, value.to_owned(), activation.context.gc_context);
-            return Ok(());
-        }
-
-        if name == "length" {
-            let length = value
-                .coerce_to_f64(activation)
-                .map(|v| v.abs() as i32)
-                .unwrap_or(0);
-            if length > 0 {
-                self.set_length(activation.context.gc_context, length as usize);
-            } else {
-                self.set_length(activation.context.gc_context, 0);
-            }
+        let this = (*self).into();
+        if let Some(index) = array_index(name) {
+            // Indexed writes bypass `set_local`, so watchers must be fired here.
+            let mut value = value;
+            let result = match self.as_script_object() {
+                Some(object) => object.call_watcher(activation, name, &mut value, this, Some(this)),
+                None => Ok(()),
+            };
+            self.set_array_element(index, value, activation.context.gc_context);
+            return result;
         }
 
-        let this = (*self).into();
         if !self.has_own_property(activation, name) {
             // Before actually inserting a new property, we need to crawl the
             // prototype chain for virtual setters.
@@ -293,7 +310,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     ///
     /// The proto is another object used to resolve methods across a class of
     /// multiple objects. It should also be accessible as `__proto__` in
-    /// `set`.
+    /// `set`, which ignores assignments that would create a prototype cycle.
     fn set_proto(&self, gc_context: MutationContext<'gc, '_>, prototype: Value<'gc>);
 
     /// Define a value on an object.
@@ -330,6 +347,21 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         clear_attributes: Attribute,
     );
 
+    /// Set the attributes of exactly the listed properties.
+    ///
+    /// Properties that do not exist on this object are ignored.
+    fn set_attributes_list(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        names: &[&str],
+        set_attributes: Attribute,
+        clear_attributes: Attribute,
+    ) {
+        for name in names {
+            self.set_attributes(gc_context, Some(*name), set_attributes, clear_attributes);
+        }
+    }
+
     /// Define a virtual property onto a given object.
     ///
     /// A virtual property is a set of get/set functions that are called when a
@@ -340,6 +372,9 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// It is not guaranteed that all objects accept virtual properties,
     /// especially if a property name conflicts with a built-in property, such
     /// as `__proto__`.
+    ///
+    /// Returns `false` if the property could not be added, such as when a
+    /// read-only or undeletable stored value already exists with that name.
     fn add_property(
         &self,
         gc_context: MutationContext<'gc, '_>,
@@ -347,7 +382,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         get: Object<'gc>,
         set: Option<Object<'gc>>,
         attributes: Attribute,
-    );
+    ) -> bool;
 
     /// Define a virtual property onto a given object.
     ///
@@ -359,6 +394,9 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// It is not guaranteed that all objects accept virtual properties,
     /// especially if a property name conflicts with a built-in property, such
     /// as `__proto__`.
+    ///
+    /// Returns `false` if the property could not be added, such as when a
+    /// read-only or undeletable stored value already exists with that name.
     fn add_property_with_case(
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
@@ -366,18 +404,19 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         get: Object<'gc>,
         set: Option<Object<'gc>>,
         attributes: Attribute,
-    );
+    ) -> bool;
 
     /// Set the 'watcher' of a given property.
     ///
     /// The property does not need to exist at the time of this being called.
+    /// Returns the watcher previously registered on the property, if any.
     fn set_watcher(
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         name: Cow<str>,
         callback: Object<'gc>,
         user_data: Value<'gc>,
-    );
+    ) -> Option<Watcher<'gc>>;
 
     /// Removed any assigned 'watcher' from the given property.
     ///
@@ -385,6 +424,11 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// called.
     fn remove_watcher(&self, activation: &mut Activation<'_, 'gc, '_>, name: Cow<str>) -> bool;
 
+    /// Removes the watchers from all properties.
+    ///
+    /// Internal watchers are left in place.
+    fn clear_watchers(&self, gc_context: MutationContext<'gc, '_>);
+
     /// Checks if the object has a given named property.
     fn has_property(&self, activation: &mut Activation<'_, 'gc, '_>, name: &str) -> bool;
 
@@ -402,6 +446,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// Enumerate the object.
     fn get_keys(&self, activation: &mut Activation<'_, 'gc, '_>) -> Vec<String>;
 
+    /// Enumerate the object's own properties in the order they were defined, without walking
+    /// the prototype chain.
+    fn own_enumerable_keys(&self, activation: &mut Activation<'_, 'gc, '_>) -> Vec<String>;
+
     /// Get the object's type string.
     fn type_of(&self) -> &'static str;
 
@@ -411,6 +459,15 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// Set the interface list for this object. (Only useful for prototypes.)
     fn set_interfaces(&self, gc_context: MutationContext<'gc, '_>, iface_list: Vec<Object<'gc>>);
 
+    /// Add an interface to this object's interface list, unless it's already there.
+    fn add_interface(&self, gc_context: MutationContext<'gc, '_>, iface: Object<'gc>) {
+        let mut iface_list = self.interfaces();
+        if !iface_list.iter().any(|i| Object::ptr_eq(*i, iface)) {
+            iface_list.push(iface);
+            self.set_interfaces(gc_context, iface_list);
+        }
+    }
+
     /// Determine if this object is an instance of a class.
     ///
     /// The class is provided in the form of its constructor function and the
@@ -571,13 +628,18 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// Check if this object is in the prototype chain of the specified test object.
     fn is_prototype_of(&self, other: Object<'gc>) -> bool {
         let mut proto = other.proto();
+        let mut depth = 0;
 
         while let Value::Object(proto_ob) = proto {
             if self.as_ptr() == proto_ob.as_ptr() {
                 return true;
             }
+            if depth == 255 {
+                return false;
+            }
 
             proto = proto_ob.proto();
+            depth += 1;
         }
 
         false
@@ -658,3 +720,29 @@ pub fn search_prototype<'gc>(
 
     Ok((Value::Undefined, None))
 }
+
+/// Whether the prototype chain starting at `proto` ends within the prototype recursion limit.
+///
+/// Cycles are rejected when `__proto__` is assigned, but native code can still create one.
+pub fn proto_chain_is_finite(mut proto: Value<'_>) -> bool {
+    let mut depth = 0;
+    while let Value::Object(p) = proto {
+        if depth == 255 {
+            return false;
+        }
+        proto = p.proto();
+        depth += 1;
+    }
+    true
+}
+
+/// Parse a property name as an array index.
+///
+/// Indices are below `u32::MAX`, so that the length of an array always fits in a `uint32`.
+/// Larger numeric names, such as `"4294967296"`, are plain properties, as in Flash.
+pub fn array_index(name: &str) -> Option<usize> {
+    name.parse::<u32>()
+        .ok()
+        .filter(|&index| index < u32::MAX)
+        .map(|index| index as usize)
+}
@@ -140,7 +140,7 @@ macro_rules! impl_custom_object {
             get: crate::avm1::object::Object<'gc>,
             set: Option<crate::avm1::object::Object<'gc>>,
             attributes: crate::avm1::property::Attribute,
-        ) {
+        ) -> bool {
             self.0
                 .read()
                 .$field
@@ -154,7 +154,7 @@ macro_rules! impl_custom_object {
             get: crate::avm1::object::Object<'gc>,
             set: Option<crate::avm1::object::Object<'gc>>,
             attributes: crate::avm1::property::Attribute,
-        ) {
+        ) -> bool {
             self.0
                 .read()
                 .$field
@@ -200,6 +200,13 @@ macro_rules! impl_custom_object {
             self.0.read().$field.get_keys(activation)
         }
 
+        fn own_enumerable_keys(
+            &self,
+            activation: &mut crate::avm1::Activation<'_, 'gc, '_>,
+        ) -> Vec<String> {
+            self.0.read().$field.own_enumerable_keys(activation)
+        }
+
         fn type_of(&self) -> &'static str {
             self.0.read().$field.type_of()
         }
@@ -269,11 +276,11 @@ macro_rules! impl_custom_object {
             name: std::borrow::Cow<str>,
             callback: crate::avm1::object::Object<'gc>,
             user_data: crate::avm1::Value<'gc>,
-        ) {
+        ) -> Option<crate::avm1::object::script_object::Watcher<'gc>> {
             self.0
                 .read()
                 .$field
-                .set_watcher(activation, name, callback, user_data);
+                .set_watcher(activation, name, callback, user_data)
         }
 
         fn remove_watcher(
@@ -283,6 +290,10 @@ macro_rules! impl_custom_object {
         ) -> bool {
             self.0.read().$field.remove_watcher(activation, name)
         }
+
+        fn clear_watchers(&self, gc_context: gc_arena::MutationContext<'gc, '_>) {
+            self.0.read().$field.clear_watchers(gc_context)
+        }
     };
 }
 
@@ -1,20 +1,77 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::ExecutionReason;
+use crate::avm1::object::{array_index, proto_chain_is_finite};
 use crate::avm1::property::{Attribute, Property};
 use crate::avm1::property_map::{Entry, PropertyMap};
 use crate::avm1::{AvmString, Object, ObjectPtr, TObject, Value};
+use crate::ecma_conversions::f64_to_wrapping_u32;
 use core::fmt;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::borrow::Cow;
 
 pub const TYPE_OF_OBJECT: &str = "object";
 
+/// Vector-backed arrays release their spare capacity when their length drops
+/// below `1 / ARRAY_SHRINK_FACTOR` of it.
+const ARRAY_SHRINK_FACTOR: usize = 4;
+
+/// Arrays longer than this switch to `ArrayStorage::Sparse`, so that a huge `length`
+/// doesn't allocate room for every element.
+const MAX_DENSE_ARRAY_LENGTH: usize = 1 << 24;
+
+/// Attributes of the `length` property of arrays, which can't be enumerated or deleted.
+const ARRAY_LENGTH_ATTRIBUTES: Attribute =
+    Attribute::from_bits_truncate(Attribute::DONT_ENUM.bits() | Attribute::DONT_DELETE.bits());
+
 #[derive(Debug, Clone, Collect)]
 #[collect(no_drop)]
 pub enum ArrayStorage<'gc> {
     Vector(Vec<Value<'gc>>),
-    Properties { length: usize },
+    Properties {
+        length: usize,
+    },
+
+    /// An array too long to store densely, whose elements only exist as properties.
+    Sparse {
+        length: usize,
+    },
+}
+
+/// The value of a property captured by `ScriptObject::properties_snapshot`.
+#[derive(Debug, Clone, Copy)]
+pub enum SnapshotValue<'gc> {
+    /// A stored value.
+    Stored(Value<'gc>),
+
+    /// A virtual property, defined by its getter and setter.
+    Virtual {
+        get: Object<'gc>,
+        set: Option<Object<'gc>>,
+    },
+}
+
+impl PartialEq for SnapshotValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SnapshotValue::Stored(a), SnapshotValue::Stored(b)) => a == b,
+            (
+                SnapshotValue::Virtual { get, set },
+                SnapshotValue::Virtual {
+                    get: other_get,
+                    set: other_set,
+                },
+            ) => {
+                Object::ptr_eq(*get, *other_get)
+                    && match (set, other_set) {
+                        (Some(set), Some(other_set)) => Object::ptr_eq(*set, *other_set),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Collect)]
@@ -32,6 +89,21 @@ impl<'gc> Watcher<'gc> {
         }
     }
 
+    /// The function called when the watched property changes.
+    pub fn callback(&self) -> Object<'gc> {
+        self.callback
+    }
+
+    /// The extra value passed to the callback.
+    pub fn user_data(&self) -> Value<'gc> {
+        self.user_data
+    }
+
+    /// Call the watcher with the property's old and new values.
+    ///
+    /// The returned value is always what gets stored, even if it is `undefined`.
+    /// There is no way to leave the property untouched; a watcher vetoes a change
+    /// by returning `old_value`.
     #[allow(clippy::too_many_arguments)]
     pub fn call(
         &self,
@@ -80,6 +152,20 @@ pub struct ScriptObjectData<'gc> {
     type_of: &'static str,
     array: ArrayStorage<'gc>,
     watchers: PropertyMap<Watcher<'gc>>,
+
+    /// Watchers registered by the host rather than by ActionScript.
+    ///
+    /// These are invisible to `Object.watch`/`unwatch` and are called after
+    /// the ActionScript watcher on the same property.
+    internal_watchers: PropertyMap<Watcher<'gc>>,
+
+    /// The properties whose watchers are currently running.
+    ///
+    /// A watcher that assigns to the property it watches doesn't trigger itself again.
+    running_watchers: Vec<String>,
+
+    /// Whether new properties are prevented from being added to this object.
+    sealed: bool,
 }
 
 impl fmt::Debug for ScriptObjectData<'_> {
@@ -89,6 +175,8 @@ impl fmt::Debug for ScriptObjectData<'_> {
             .field("values", &self.values)
             .field("array", &self.array)
             .field("watchers", &self.watchers)
+            .field("internal_watchers", &self.internal_watchers)
+            .field("sealed", &self.sealed)
             .finish()
     }
 }
@@ -97,16 +185,50 @@ impl<'gc> ScriptObject<'gc> {
     pub fn object(
         gc_context: MutationContext<'gc, '_>,
         proto: Option<Object<'gc>>,
+    ) -> ScriptObject<'gc> {
+        Self::object_with_capacity(gc_context, proto, 0)
+    }
+
+    /// Constructs an object with room for `capacity` properties.
+    pub fn object_with_capacity(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+        capacity: usize,
     ) -> ScriptObject<'gc> {
         ScriptObject(GcCell::allocate(
             gc_context,
             ScriptObjectData {
                 prototype: proto.map_or(Value::Undefined, Value::Object),
                 type_of: TYPE_OF_OBJECT,
+                values: PropertyMap::with_capacity(capacity),
+                array: ArrayStorage::Properties { length: 0 },
+                interfaces: vec![],
+                watchers: PropertyMap::new(),
+                internal_watchers: PropertyMap::new(),
+                running_watchers: Vec::new(),
+                sealed: false,
+            },
+        ))
+    }
+
+    /// Constructs an object with a custom `typeof` string, such as `"movieclip"`.
+    pub fn object_with_type(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+        type_of: &'static str,
+    ) -> ScriptObject<'gc> {
+        ScriptObject(GcCell::allocate(
+            gc_context,
+            ScriptObjectData {
+                prototype: proto.map_or(Value::Undefined, Value::Object),
+                type_of,
                 values: PropertyMap::new(),
                 array: ArrayStorage::Properties { length: 0 },
                 interfaces: vec![],
                 watchers: PropertyMap::new(),
+                internal_watchers: PropertyMap::new(),
+                running_watchers: Vec::new(),
+                sealed: false,
             },
         ))
     }
@@ -114,19 +236,48 @@ impl<'gc> ScriptObject<'gc> {
     pub fn array(
         gc_context: MutationContext<'gc, '_>,
         proto: Option<Object<'gc>>,
+    ) -> ScriptObject<'gc> {
+        Self::array_with_capacity(gc_context, proto, 0)
+    }
+
+    /// Constructs an empty array with room for `capacity` elements.
+    pub fn array_with_capacity(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+        capacity: usize,
     ) -> ScriptObject<'gc> {
         let object = ScriptObject(GcCell::allocate(
             gc_context,
             ScriptObjectData {
                 prototype: proto.map_or(Value::Undefined, Value::Object),
                 type_of: TYPE_OF_OBJECT,
-                values: PropertyMap::new(),
-                array: ArrayStorage::Vector(Vec::new()),
+                // Elements are mirrored as properties, alongside `length`.
+                values: PropertyMap::with_capacity(capacity + 1),
+                array: ArrayStorage::Vector(Vec::with_capacity(capacity)),
                 interfaces: vec![],
                 watchers: PropertyMap::new(),
+                internal_watchers: PropertyMap::new(),
+                running_watchers: Vec::new(),
+                sealed: false,
             },
         ));
-        object.sync_native_property("length", gc_context, Some(0.into()), false);
+        object.sync_native_property(
+            "length",
+            gc_context,
+            Some(0.into()),
+            ARRAY_LENGTH_ATTRIBUTES,
+        );
+        object
+    }
+
+    /// Constructs an array holding `elements`.
+    pub fn array_with_elements(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+        elements: &[Value<'gc>],
+    ) -> ScriptObject<'gc> {
+        let object = Self::array_with_capacity(gc_context, proto, elements.len());
+        object.set_array_elements(0, elements, gc_context);
         object
     }
 
@@ -144,6 +295,9 @@ impl<'gc> ScriptObject<'gc> {
                 array: ArrayStorage::Properties { length: 0 },
                 interfaces: vec![],
                 watchers: PropertyMap::new(),
+                internal_watchers: PropertyMap::new(),
+                running_watchers: Vec::new(),
+                sealed: false,
             },
         ))
         .into()
@@ -164,6 +318,9 @@ impl<'gc> ScriptObject<'gc> {
                 array: ArrayStorage::Properties { length: 0 },
                 interfaces: vec![],
                 watchers: PropertyMap::new(),
+                internal_watchers: PropertyMap::new(),
+                running_watchers: Vec::new(),
+                sealed: false,
             },
         ))
     }
@@ -172,15 +329,408 @@ impl<'gc> ScriptObject<'gc> {
         self.0.write(gc_context).type_of = type_of;
     }
 
+    /// Seal this object, as in `Object.seal`.
+    ///
+    /// All existing properties become non-deletable and no new properties
+    /// may be added, but writable values can still be overwritten.
+    pub fn seal(&self, gc_context: MutationContext<'gc, '_>) {
+        let mut object = self.0.write(gc_context);
+        for (_name, prop) in object.values.iter_mut() {
+            prop.set_attributes(prop.attributes() | Attribute::DONT_DELETE);
+        }
+        object.sealed = true;
+    }
+
+    /// Whether this object has been sealed.
+    pub fn is_sealed(&self) -> bool {
+        self.0.read().sealed
+    }
+
+    /// Remove all properties, array elements and watchers from this object.
+    ///
+    /// The prototype, interfaces and `typeof` are kept, so the object can be reused as if it
+    /// were freshly created with the same prototype. Arrays stay arrays, with a `length` of 0.
+    pub fn clear(&self, gc_context: MutationContext<'gc, '_>) {
+        let is_array = self.is_array_backed();
+        let mut object = self.0.write(gc_context);
+        object.values = PropertyMap::new();
+        object.array = if is_array {
+            ArrayStorage::Vector(Vec::new())
+        } else {
+            ArrayStorage::Properties { length: 0 }
+        };
+        object.watchers = PropertyMap::new();
+        object.internal_watchers = PropertyMap::new();
+        object.running_watchers.clear();
+        object.sealed = false;
+        drop(object);
+
+        if is_array {
+            self.sync_native_property(
+                "length",
+                gc_context,
+                Some(0.into()),
+                ARRAY_LENGTH_ATTRIBUTES,
+            );
+        }
+    }
+
+    /// The number of elements the array storage can hold without reallocating.
+    ///
+    /// Objects without vector storage have no capacity.
+    pub fn array_capacity(&self) -> usize {
+        match &self.0.read().array {
+            ArrayStorage::Vector(vector) => vector.capacity(),
+            ArrayStorage::Properties { .. } | ArrayStorage::Sparse { .. } => 0,
+        }
+    }
+
+    /// Whether this object is a genuine `Array` instance, backed by vector storage
+    /// (or sparse storage, for very long arrays).
+    ///
+    /// Objects that merely have `Array.prototype` in their prototype chain, or
+    /// that define a numeric `length`, are not considered arrays.
+    pub fn is_array_backed(&self) -> bool {
+        matches!(
+            self.0.read().array,
+            ArrayStorage::Vector(_) | ArrayStorage::Sparse { .. }
+        )
+    }
+
+    /// Call the watchers registered on a named property, if any.
+    ///
+    /// The ActionScript watcher runs first, followed by the internal watcher.
+    /// `value` is replaced with the value returned by each watcher. A thrown
+    /// error is returned only after `value` has been cleared to `undefined`.
+    pub fn call_watcher(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &str,
+        value: &mut Value<'gc>,
+        this: Object<'gc>,
+        base_proto: Option<Object<'gc>>,
+    ) -> Result<(), Error<'gc>> {
+        let case_sensitive = activation.is_case_sensitive();
+        let watchers = {
+            let object = self.0.read();
+            let is_running = object.running_watchers.iter().any(|running| {
+                if case_sensitive {
+                    running == name
+                } else {
+                    running.eq_ignore_ascii_case(name)
+                }
+            });
+            if is_running {
+                return Ok(());
+            }
+            [
+                object.watchers.get(name, case_sensitive).cloned(),
+                object.internal_watchers.get(name, case_sensitive).cloned(),
+            ]
+        };
+        if watchers.iter().all(Option::is_none) {
+            return Ok(());
+        }
+
+        self.0
+            .write(activation.context.gc_context)
+            .running_watchers
+            .push(name.to_string());
+        let mut result = Ok(());
+        for watcher in watchers.iter().flatten() {
+            let old_value = match self.get(name, activation) {
+                Ok(old_value) => old_value,
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            };
+            match watcher.call(activation, name, old_value, *value, this, base_proto) {
+                Ok(v) => *value = v,
+                Err(Error::ThrownValue(e)) => {
+                    *value = Value::Undefined;
+                    result = Err(Error::ThrownValue(e));
+                }
+                Err(_) => *value = Value::Undefined,
+            };
+        }
+        self.0
+            .write(activation.context.gc_context)
+            .running_watchers
+            .pop();
+        result
+    }
+
+    /// Set an internal watcher on a property.
+    ///
+    /// Internal watchers coexist with the watcher set by `Object.watch`, and
+    /// are not removed by `Object.unwatch`.
+    pub fn set_internal_watcher(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: Cow<str>,
+        callback: Object<'gc>,
+        user_data: Value<'gc>,
+    ) {
+        self.0
+            .write(activation.context.gc_context)
+            .internal_watchers
+            .insert(
+                &name,
+                Watcher::new(callback, user_data),
+                activation.is_case_sensitive(),
+            );
+    }
+
+    /// Remove an internal watcher from a property.
+    ///
+    /// Returns whether an internal watcher was present.
+    pub fn remove_internal_watcher(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: Cow<str>,
+    ) -> bool {
+        self.0
+            .write(activation.context.gc_context)
+            .internal_watchers
+            .remove(name.as_ref(), activation.is_case_sensitive())
+            .is_some()
+    }
+
+    /// Define several values on this object at once.
+    ///
+    /// This behaves like calling `define_value` for each entry in order, but only borrows the
+    /// object once. Names are matched with the given case sensitivity, so a later entry
+    /// replaces an earlier one that differs only in case when `case_sensitive` is false.
+    pub fn define_values(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        entries: &[(&str, Value<'gc>, Attribute)],
+        case_sensitive: bool,
+    ) {
+        let mut object = self.0.write(gc_context);
+        object.values.reserve(entries.len());
+        for &(name, value, attributes) in entries {
+            if object.sealed && !object.values.contains_key(name, case_sensitive) {
+                continue;
+            }
+            object
+                .values
+                .insert(name, Property::Stored { value, attributes }, case_sensitive);
+        }
+    }
+
+    /// Capture the properties of this object, in the order they were defined.
+    ///
+    /// Virtual properties are captured as `SnapshotValue::Virtual` markers holding their getter
+    /// and setter.
+    pub fn properties_snapshot(&self) -> Vec<(String, SnapshotValue<'gc>, Attribute)> {
+        let mut snapshot: Vec<_> = self
+            .0
+            .read()
+            .values
+            .iter()
+            .map(|(name, property)| match property {
+                Property::Stored { value, attributes } => {
+                    (name.clone(), SnapshotValue::Stored(*value), *attributes)
+                }
+                Property::Virtual {
+                    get,
+                    set,
+                    attributes,
+                } => (
+                    name.clone(),
+                    SnapshotValue::Virtual {
+                        get: *get,
+                        set: *set,
+                    },
+                    *attributes,
+                ),
+            })
+            .collect();
+        snapshot.reverse();
+        snapshot
+    }
+
+    /// Replace the properties of this object with a snapshot from `properties_snapshot`.
+    ///
+    /// All existing properties are cleared first, and virtual properties are restored from their
+    /// markers. The elements of an array are rebuilt from the restored `length` and index
+    /// properties.
+    pub fn restore_properties(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        properties: &[(String, SnapshotValue<'gc>, Attribute)],
+    ) {
+        let mut object = self.0.write(gc_context);
+        let mut values = PropertyMap::with_capacity(properties.len());
+        for (name, value, attributes) in properties {
+            let attributes = *attributes;
+            let property = match *value {
+                SnapshotValue::Stored(value) => Property::Stored { value, attributes },
+                SnapshotValue::Virtual { get, set } => Property::Virtual {
+                    get,
+                    set,
+                    attributes,
+                },
+            };
+            values.insert(name, property, true);
+        }
+
+        let length = match values.get("length", true) {
+            Some(Property::Stored {
+                value: Value::Number(length),
+                ..
+            }) => f64_to_wrapping_u32(*length) as usize,
+            _ => 0,
+        };
+        object.array = match object.array {
+            ArrayStorage::Vector(_) | ArrayStorage::Sparse { .. }
+                if length > MAX_DENSE_ARRAY_LENGTH =>
+            {
+                ArrayStorage::Sparse { length }
+            }
+            ArrayStorage::Vector(_) | ArrayStorage::Sparse { .. } => ArrayStorage::Vector(
+                (0..length)
+                    .map(|i| match values.get_numeric(i) {
+                        Some(Property::Stored { value, .. }) => *value,
+                        _ => Value::Undefined,
+                    })
+                    .collect(),
+            ),
+            ArrayStorage::Properties { .. } => ArrayStorage::Properties { length },
+        };
+        object.values = values;
+    }
+
+    /// Add a virtual property, unless a read-only or undeletable stored value is in the way.
+    fn add_virtual(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        name: &str,
+        get: Object<'gc>,
+        set: Option<Object<'gc>>,
+        attributes: Attribute,
+        case_sensitive: bool,
+    ) -> bool {
+        let property = Property::Virtual {
+            get,
+            set,
+            attributes,
+        };
+        let mut object = self.0.write(gc_context);
+        let sealed = object.sealed;
+        match object.values.entry(name, case_sensitive) {
+            Entry::Occupied(mut entry) => {
+                let existing = entry.get_mut();
+                if !existing.is_virtual()
+                    && existing
+                        .attributes()
+                        .intersects(Attribute::READ_ONLY | Attribute::DONT_DELETE)
+                {
+                    return false;
+                }
+                entry.insert(property);
+            }
+            Entry::Vacant(_) if sealed => return false,
+            Entry::Vacant(entry) => entry.insert(property),
+        }
+        true
+    }
+
+    /// Whether the array element at `index` holds a value, rather than being a hole.
+    ///
+    /// Every stored element is mirrored by an own property, which deleting the element removes,
+    /// so a slot below the length without one is a hole. Elements inherited from the prototype
+    /// don't count.
+    pub fn array_element_present(&self, index: usize) -> bool {
+        let object = self.0.read();
+        let length = match &object.array {
+            ArrayStorage::Vector(vector) => vector.len(),
+            ArrayStorage::Properties { length } | ArrayStorage::Sparse { length } => *length,
+        };
+        index < length && object.values.contains_key(&index.to_string(), true)
+    }
+
+    /// Converts this object to `ArrayStorage::Vector`, so it behaves as a real array.
+    ///
+    /// Any elements already stored as properties are copied into the vector.
+    pub fn promote_to_array(&self, gc_context: MutationContext<'gc, '_>) {
+        let length = match self.0.read().array {
+            ArrayStorage::Vector(_) | ArrayStorage::Sparse { .. } => return,
+            ArrayStorage::Properties { length } => length,
+        };
+        let elements = (0..length).map(|i| self.array_element(i)).collect();
+        self.0.write(gc_context).array = ArrayStorage::Vector(elements);
+        self.sync_native_property(
+            "length",
+            gc_context,
+            Some(length.into()),
+            ARRAY_LENGTH_ATTRIBUTES,
+        );
+    }
+
+    /// Sets the elements of this array starting at index `start` to `elements`.
+    ///
+    /// This is equivalent to calling `set_array_element` for each element, but only syncs
+    /// `length` once.
+    pub fn set_array_elements(
+        &self,
+        start: usize,
+        elements: &[Value<'gc>],
+        gc_context: MutationContext<'gc, '_>,
+    ) {
+        if self.is_sealed() {
+            // Only existing elements may be replaced, so each one is checked individually.
+            for (i, value) in elements.iter().enumerate() {
+                self.set_array_element(start + i, *value, gc_context);
+            }
+            return;
+        }
+        for (i, value) in elements.iter().enumerate() {
+            self.sync_native_property(
+                &(start + i).to_string(),
+                gc_context,
+                Some(*value),
+                Attribute::empty(),
+            );
+        }
+        let end = start + elements.len();
+        let length = match &mut self.0.write(gc_context).array {
+            ArrayStorage::Vector(vector) => {
+                if vector.len() < end {
+                    vector.resize(end, Value::Undefined);
+                }
+                vector[start..end].copy_from_slice(elements);
+                Some(vector.len())
+            }
+            ArrayStorage::Sparse { length } => {
+                *length = (*length).max(end);
+                Some(*length)
+            }
+            ArrayStorage::Properties { .. } => None,
+        };
+        if let Some(length) = length {
+            self.sync_native_property(
+                "length",
+                gc_context,
+                Some(length.into()),
+                ARRAY_LENGTH_ATTRIBUTES,
+            );
+        }
+    }
+
     #[allow(clippy::trivially_copy_pass_by_ref)]
     pub fn sync_native_property(
         &self,
         name: &str,
         gc_context: MutationContext<'gc, '_>,
         native_value: Option<Value<'gc>>,
-        is_enumerable: bool,
+        attributes: Attribute,
     ) {
-        match self.0.write(gc_context).values.entry(name, false) {
+        let mut object = self.0.write(gc_context);
+        let sealed = object.sealed;
+        match object.values.entry(name, false) {
             Entry::Occupied(mut entry) => {
                 if let Property::Stored { value, .. } = entry.get_mut() {
                     match native_value {
@@ -193,15 +743,12 @@ impl<'gc> ScriptObject<'gc> {
                     }
                 }
             }
+            Entry::Vacant(_) if sealed => {}
             Entry::Vacant(entry) => {
                 if let Some(native_value) = native_value {
                     entry.insert(Property::Stored {
                         value: native_value,
-                        attributes: if is_enumerable {
-                            Attribute::empty()
-                        } else {
-                            Attribute::DONT_ENUM
-                        },
+                        attributes,
                     });
                 }
             }
@@ -268,32 +815,40 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         this: Object<'gc>,
         base_proto: Option<Object<'gc>>,
     ) -> Result<(), Error<'gc>> {
-        let watcher = self
-            .0
-            .read()
-            .watchers
-            .get(name, activation.is_case_sensitive())
-            .cloned();
-        let mut result = Ok(());
-        if let Some(watcher) = watcher {
-            let old_value = self.get(name, activation)?;
-            match watcher.call(activation, name, old_value, value, this, base_proto) {
-                Ok(v) => value = v,
-                Err(Error::ThrownValue(e)) => {
-                    value = Value::Undefined;
-                    result = Err(Error::ThrownValue(e));
-                }
-                Err(_) => value = Value::Undefined,
-            };
+        let result = self.call_watcher(activation, name, &mut value, this, base_proto);
+
+        let is_length = if activation.is_case_sensitive() {
+            name == "length"
+        } else {
+            name.eq_ignore_ascii_case("length")
+        };
+        if is_length {
+            // Array lengths wrap as `uint32`, matching `Array.push` and `Array.unshift`.
+            let length = value
+                .coerce_to_f64(activation)
+                .map(f64_to_wrapping_u32)
+                .unwrap_or(0) as usize;
+            if self.is_array_backed() {
+                self.set_length(activation.context.gc_context, length);
+
+                // Arrays keep their synced numeric `length` rather than the raw value.
+                return result;
+            }
+
+            // Other objects store the raw value, but still track the length so that array
+            // methods called on them (such as `Array.prototype.push.call(object)`) can use it.
+            if let ArrayStorage::Properties { length: stored } =
+                &mut self.0.write(activation.context.gc_context).array
+            {
+                *stored = length;
+            }
         }
 
-        let setter = match self
-            .0
-            .write(activation.context.gc_context)
-            .values
-            .entry(name, activation.is_case_sensitive())
-        {
+        let mut object = self.0.write(activation.context.gc_context);
+        let sealed = object.sealed;
+        let setter = match object.values.entry(name, activation.is_case_sensitive()) {
             Entry::Occupied(mut entry) => entry.get_mut().set(value),
+            Entry::Vacant(_) if sealed => None,
             Entry::Vacant(entry) => {
                 entry.insert(Property::Stored {
                     value,
@@ -302,6 +857,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
                 None
             }
         };
+        drop(object);
 
         if let Some(setter) = setter {
             if let Some(exec) = setter.as_executable() {
@@ -361,7 +917,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         this: Object<'gc>,
     ) -> Result<Object<'gc>, Error<'gc>> {
         match self.0.read().array {
-            ArrayStorage::Vector(_) => {
+            ArrayStorage::Vector(_) | ArrayStorage::Sparse { .. } => {
                 Ok(ScriptObject::array(activation.context.gc_context, Some(this)).into())
             }
             ArrayStorage::Properties { .. } => {
@@ -378,6 +934,14 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         if let Some(prop) = object.values.get(name, activation.is_case_sensitive()) {
             if prop.can_delete() {
                 object.values.remove(name, activation.is_case_sensitive());
+                // Deleting an element leaves a hole, but never changes the array's length.
+                if let (ArrayStorage::Vector(vector), Some(index)) =
+                    (&mut object.array, array_index(name))
+                {
+                    if let Some(value) = vector.get_mut(index) {
+                        *value = Value::Undefined;
+                    }
+                }
                 return true;
             }
         }
@@ -392,16 +956,8 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         get: Object<'gc>,
         set: Option<Object<'gc>>,
         attributes: Attribute,
-    ) {
-        self.0.write(gc_context).values.insert(
-            name,
-            Property::Virtual {
-                get,
-                set,
-                attributes,
-            },
-            false,
-        );
+    ) -> bool {
+        self.add_virtual(gc_context, name, get, set, attributes, false)
     }
 
     fn add_property_with_case(
@@ -411,16 +967,15 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         get: Object<'gc>,
         set: Option<Object<'gc>>,
         attributes: Attribute,
-    ) {
-        self.0.write(activation.context.gc_context).values.insert(
+    ) -> bool {
+        self.add_virtual(
+            activation.context.gc_context,
             name,
-            Property::Virtual {
-                get,
-                set,
-                attributes,
-            },
+            get,
+            set,
+            attributes,
             activation.is_case_sensitive(),
-        );
+        )
     }
 
     fn set_watcher(
@@ -429,12 +984,12 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         name: Cow<str>,
         callback: Object<'gc>,
         user_data: Value<'gc>,
-    ) {
+    ) -> Option<Watcher<'gc>> {
         self.0.write(activation.context.gc_context).watchers.insert(
             &name,
             Watcher::new(callback, user_data),
             activation.is_case_sensitive(),
-        );
+        )
     }
 
     fn remove_watcher(&self, activation: &mut Activation<'_, 'gc, '_>, name: Cow<str>) -> bool {
@@ -446,6 +1001,10 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         old.is_some()
     }
 
+    fn clear_watchers(&self, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).watchers = PropertyMap::new();
+    }
+
     fn define_value(
         &self,
         gc_context: MutationContext<'gc, '_>,
@@ -453,8 +1012,11 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         value: Value<'gc>,
         attributes: Attribute,
     ) {
-        self.0
-            .write(gc_context)
+        let mut object = self.0.write(gc_context);
+        if object.sealed && !object.values.contains_key(name, true) {
+            return;
+        }
+        object
             .values
             .insert(name, Property::Stored { value, attributes }, true);
     }
@@ -488,29 +1050,36 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
     }
 
     fn set_proto(&self, gc_context: MutationContext<'gc, '_>, prototype: Value<'gc>) {
+        if let Value::Object(proto) = prototype {
+            if proto.as_ptr() == self.as_ptr() || self.is_prototype_of(proto) {
+                return;
+            }
+        }
         self.0.write(gc_context).prototype = prototype;
     }
 
     /// Checks if the object has a given named property.
     fn has_property(&self, activation: &mut Activation<'_, 'gc, '_>, name: &str) -> bool {
-        self.has_own_property(activation, name)
-            || if let Value::Object(proto) = self.proto() {
+        if self.has_own_property(activation, name) {
+            return true;
+        }
+        match self.proto() {
+            Value::Object(proto) if proto_chain_is_finite(self.proto()) => {
                 proto.has_property(activation, name)
-            } else {
-                false
             }
+            _ => false,
+        }
     }
 
     /// Checks if the object has a given named property on itself (and not,
     /// say, the object's prototype or superclass)
     fn has_own_property(&self, activation: &mut Activation<'_, 'gc, '_>, name: &str) -> bool {
-        if name == "__proto__" {
-            return true;
-        }
+        // `__proto__` is synthesized when it isn't stored on the object.
         self.0
             .read()
             .values
             .contains_key(name, activation.is_case_sensitive())
+            || name == "__proto__"
     }
 
     fn has_own_virtual(&self, activation: &mut Activation<'_, 'gc, '_>, name: &str) -> bool {
@@ -541,11 +1110,16 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
     }
 
     /// Enumerate the object.
+    ///
+    /// Prototype keys come first, followed by own keys in `PropertyMap`
+    /// iteration order (most recently added first), which is the order Flash
+    /// Player uses for `for..in`.
     fn get_keys(&self, activation: &mut Activation<'_, 'gc, '_>) -> Vec<String> {
-        let proto_keys = if let Value::Object(proto) = self.proto() {
-            proto.get_keys(activation)
-        } else {
-            Vec::new()
+        let proto_keys = match self.proto() {
+            Value::Object(proto) if proto_chain_is_finite(self.proto()) => {
+                proto.get_keys(activation)
+            }
+            _ => Vec::new(),
         };
         let mut out_keys = vec![];
         let object = self.0.read();
@@ -558,18 +1132,43 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         }));
 
         // Then our own keys.
-        out_keys.extend(self.0.read().values.iter().filter_map(move |(k, p)| {
+        let own_keys = object.values.iter().filter_map(move |(k, p)| {
             if p.is_enumerable() {
                 Some(k.to_string())
             } else {
                 None
             }
-        }));
+        });
+
+        if let ArrayStorage::Vector(_) | ArrayStorage::Sparse { .. } = object.array {
+            // Arrays enumerate their indices in ascending order, before any other keys.
+            let (mut indices, names): (Vec<_>, Vec<_>) =
+                own_keys.partition(|k| array_index(k).is_some());
+            indices.sort_by_key(|k| array_index(k));
+            out_keys.extend(indices);
+            out_keys.extend(names);
+        } else {
+            out_keys.extend(own_keys);
+        }
 
         out_keys
     }
 
-    fn type_of(&self) -> &'static str {
+    fn own_enumerable_keys(&self, _activation: &mut Activation<'_, 'gc, '_>) -> Vec<String> {
+        let mut keys: Vec<_> = self
+            .0
+            .read()
+            .values
+            .iter()
+            .filter(|(_, p)| p.is_enumerable())
+            .map(|(k, _)| k.to_string())
+            .collect();
+        // `PropertyMap` iterates the most recently defined properties first.
+        keys.reverse();
+        keys
+    }
+
+    fn type_of(&self) -> &'static str {
         self.0.read().type_of
     }
 
@@ -578,6 +1177,17 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
     }
 
     fn set_interfaces(&self, gc_context: MutationContext<'gc, '_>, iface_list: Vec<Object<'gc>>) {
+        // Only constructor functions can be implemented as interfaces.
+        let iface_list = iface_list
+            .into_iter()
+            .filter(|iface| {
+                let is_function = iface.as_executable().is_some();
+                if !is_function {
+                    log::warn!("Ignoring interface that is not a function");
+                }
+                is_function
+            })
+            .collect();
         self.0.write(gc_context).interfaces = iface_list;
     }
 
@@ -592,37 +1202,73 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
     fn length(&self) -> usize {
         match &self.0.read().array {
             ArrayStorage::Vector(vector) => vector.len(),
-            ArrayStorage::Properties { length } => *length,
+            ArrayStorage::Properties { length } | ArrayStorage::Sparse { length } => *length,
         }
     }
 
     fn set_length(&self, gc_context: MutationContext<'gc, '_>, new_length: usize) {
-        let mut to_remove = None;
+        let mut object = self.0.write(gc_context);
+        let old_length = match &object.array {
+            ArrayStorage::Vector(vector) => vector.len(),
+            ArrayStorage::Properties { length } | ArrayStorage::Sparse { length } => *length,
+        };
+        if new_length > MAX_DENSE_ARRAY_LENGTH {
+            if let ArrayStorage::Vector(_) = object.array {
+                // Every element is also stored as a property, so nothing is lost.
+                object.array = ArrayStorage::Sparse { length: new_length };
+            }
+        }
 
-        match &mut self.0.write(gc_context).array {
+        // Elements at or beyond the new length are removed from the properties. The keys are
+        // collected rather than counted, since a sparse length can be huge.
+        let to_remove: Vec<String> = if new_length < old_length {
+            object
+                .values
+                .iter()
+                .filter(|(name, _)| array_index(name).map_or(false, |i| i >= new_length))
+                .map(|(name, _)| name.clone())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut make_dense = false;
+        match &mut object.array {
             ArrayStorage::Vector(vector) => {
-                let old_length = vector.len();
                 vector.resize(new_length, Value::Undefined);
-                if new_length < old_length {
-                    to_remove = Some(new_length..old_length);
+                // Release excess capacity once the array is much smaller than its allocation.
+                if vector.capacity() / ARRAY_SHRINK_FACTOR > new_length {
+                    vector.shrink_to_fit();
                 }
             }
+            ArrayStorage::Sparse { length } => {
+                *length = new_length;
+                make_dense = new_length <= MAX_DENSE_ARRAY_LENGTH;
+            }
             ArrayStorage::Properties { length } => {
                 *length = new_length;
             }
         }
-        if let Some(to_remove) = to_remove {
-            for i in to_remove {
-                self.sync_native_property(&i.to_string(), gc_context, None, true);
-            }
+        drop(object);
+        for name in to_remove {
+            self.sync_native_property(&name, gc_context, None, Attribute::empty());
+        }
+        if make_dense {
+            let elements = (0..new_length).map(|i| self.array_element(i)).collect();
+            self.0.write(gc_context).array = ArrayStorage::Vector(elements);
         }
-        self.sync_native_property("length", gc_context, Some(new_length.into()), false);
+        self.sync_native_property(
+            "length",
+            gc_context,
+            Some(new_length.into()),
+            ARRAY_LENGTH_ATTRIBUTES,
+        );
     }
 
     fn array(&self) -> Vec<Value<'gc>> {
         match &self.0.read().array {
             ArrayStorage::Vector(vector) => vector.to_owned(),
-            ArrayStorage::Properties { length } => {
+            ArrayStorage::Properties { length } | ArrayStorage::Sparse { length } => {
                 let mut values = Vec::new();
                 for i in 0..*length {
                     values.push(self.array_element(i));
@@ -641,10 +1287,10 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
                     Value::Undefined
                 }
             }
-            ArrayStorage::Properties { length } => {
+            ArrayStorage::Properties { length } | ArrayStorage::Sparse { length } => {
                 if index < *length {
                     if let Some(Property::Stored { value, .. }) =
-                        self.0.read().values.get(&index.to_string(), false)
+                        self.0.read().values.get_numeric(index)
                     {
                         return value.to_owned();
                     }
@@ -660,7 +1306,19 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         value: Value<'gc>,
         gc_context: MutationContext<'gc, '_>,
     ) -> usize {
-        self.sync_native_property(&index.to_string(), gc_context, Some(value), true);
+        let name = index.to_string();
+        {
+            let object = self.0.read();
+            if object.sealed && !object.values.contains_key(&name, true) {
+                return match &object.array {
+                    ArrayStorage::Vector(vector) => vector.len(),
+                    ArrayStorage::Properties { length } | ArrayStorage::Sparse { length } => {
+                        *length
+                    }
+                };
+            }
+        }
+        self.sync_native_property(&name, gc_context, Some(value), Attribute::empty());
         let mut adjust_length = false;
         let length = match &mut self.0.write(gc_context).array {
             ArrayStorage::Vector(vector) => {
@@ -671,23 +1329,106 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
                 adjust_length = true;
                 vector.len()
             }
+            ArrayStorage::Sparse { length } => {
+                if index >= *length {
+                    *length = index + 1;
+                    adjust_length = true;
+                }
+                *length
+            }
             ArrayStorage::Properties { length } => *length,
         };
         if adjust_length {
-            self.sync_native_property("length", gc_context, Some(length.into()), false);
+            self.sync_native_property(
+                "length",
+                gc_context,
+                Some(length.into()),
+                ARRAY_LENGTH_ATTRIBUTES,
+            );
         }
         length
     }
 
     fn delete_array_element(&self, index: usize, gc_context: MutationContext<'gc, '_>) {
-        if let ArrayStorage::Vector(vector) = &mut self.0.write(gc_context).array {
-            if index < vector.len() {
-                vector[index] = Value::Undefined;
+        let deleted = match &mut self.0.write(gc_context).array {
+            ArrayStorage::Vector(vector) => {
+                if index < vector.len() {
+                    vector[index] = Value::Undefined;
+                    true
+                } else {
+                    false
+                }
             }
+            ArrayStorage::Sparse { length } => index < *length,
+            ArrayStorage::Properties { .. } => false,
+        };
+        // Remove the synced property too, so that the hole isn't enumerated.
+        if deleted {
+            self.sync_native_property(&index.to_string(), gc_context, None, Attribute::empty());
         }
     }
 }
 
+/// Compare two objects for structural equality.
+///
+/// Enumerable own properties are compared recursively, and arrays must also
+/// agree on their length. Cycles are handled by treating any pair of objects
+/// that is already being compared as equal.
+pub fn deep_equals<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    a: Object<'gc>,
+    b: Object<'gc>,
+) -> bool {
+    deep_equals_objects(activation, a, b, &mut Vec::new())
+}
+
+fn deep_equals_objects<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    a: Object<'gc>,
+    b: Object<'gc>,
+    visited: &mut Vec<(*const ObjectPtr, *const ObjectPtr)>,
+) -> bool {
+    if Object::ptr_eq(a, b) {
+        return true;
+    }
+
+    let pair = (a.as_ptr(), b.as_ptr());
+    if visited.contains(&pair) {
+        return true;
+    }
+    visited.push(pair);
+
+    let is_array = |o: Object<'gc>| o.as_script_object().map_or(false, |o| o.is_array_backed());
+    if is_array(a) != is_array(b) || (is_array(a) && a.length() != b.length()) {
+        return false;
+    }
+
+    let a_keys = a.own_enumerable_keys(activation);
+    let b_keys = b.own_enumerable_keys(activation);
+    if a_keys.len() != b_keys.len() {
+        return false;
+    }
+
+    for key in a_keys {
+        if !b.has_own_property(activation, &key) {
+            return false;
+        }
+        let a_value = a.get(&key, activation).unwrap_or(Value::Undefined);
+        let b_value = b.get(&key, activation).unwrap_or(Value::Undefined);
+        let equal = match (a_value, b_value) {
+            (Value::Object(a_value), Value::Object(b_value)) => {
+                deep_equals_objects(activation, a_value, b_value, visited)
+            }
+            (a_value, b_value) => a_value == b_value,
+        };
+        if !equal {
+            return false;
+        }
+    }
+
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -957,6 +1698,32 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_delete_case_insensitive() {
+        with_object(5, |activation, object| {
+            object.set("foo", "Stored!".into(), activation).unwrap();
+            object.set("Bar", "Stored!".into(), activation).unwrap();
+
+            assert!(object.delete(activation, "Foo"));
+            assert!(object.delete(activation, "BAR"));
+            assert!(!object.has_own_property(activation, "foo"));
+            assert!(!object.has_own_property(activation, "Bar"));
+            assert_eq!(object.get("foo", activation).unwrap(), Value::Undefined);
+        })
+    }
+
+    #[test]
+    fn test_delete_case_sensitive() {
+        with_object(7, |activation, object| {
+            object.set("foo", "Stored!".into(), activation).unwrap();
+
+            assert!(!object.delete(activation, "Foo"));
+            assert_eq!(object.get("foo", activation).unwrap(), "Stored!".into());
+            assert!(object.delete(activation, "foo"));
+            assert_eq!(object.get("foo", activation).unwrap(), Value::Undefined);
+        })
+    }
+
     #[test]
     fn test_iter_values() {
         with_object(0, |activation, object| {
@@ -1002,4 +1769,972 @@ mod tests {
             assert!(!keys.contains(&"virtual_hidden".to_string()));
         })
     }
+
+    #[test]
+    fn test_seal() {
+        with_object(0, |activation, object| {
+            object
+                .set("existing", "initial".into(), activation)
+                .unwrap();
+            object
+                .as_script_object()
+                .unwrap()
+                .seal(activation.context.gc_context);
+
+            object
+                .set("existing", "replaced".into(), activation)
+                .unwrap();
+            object.set("new", "value".into(), activation).unwrap();
+
+            assert_eq!(
+                object.get("existing", activation).unwrap(),
+                "replaced".into()
+            );
+            assert!(!object.has_own_property(activation, "new"));
+            assert_eq!(object.get("new", activation).unwrap(), Value::Undefined);
+            assert!(!object.delete(activation, "existing"));
+
+            object.set("5", "element".into(), activation).unwrap();
+            assert!(!object.has_own_property(activation, "5"));
+            object.define_value(
+                activation.context.gc_context,
+                "defined",
+                1.into(),
+                Attribute::empty(),
+            );
+            assert!(!object.has_own_property(activation, "defined"));
+            assert!(!object.add_property(
+                activation.context.gc_context,
+                "virtual",
+                object,
+                None,
+                Attribute::empty(),
+            ));
+            assert!(!object.has_own_property(activation, "virtual"));
+        })
+    }
+
+    #[test]
+    fn test_seal_array() {
+        with_object(0, |activation, _object| {
+            let gc_context = activation.context.gc_context;
+            let array =
+                ScriptObject::array(gc_context, Some(activation.context.avm1.prototypes.array));
+            array.set_array_elements(0, &[1.into(), 2.into()], gc_context);
+            array.seal(gc_context);
+
+            array.set("1", 20.into(), activation).unwrap();
+            array.set("5", 50.into(), activation).unwrap();
+            array.set_array_elements(1, &[21.into(), 30.into()], gc_context);
+
+            assert_eq!(array.length(), 2);
+            assert_eq!(array.array_element(1), 21.into());
+            assert!(!array.has_own_property(activation, "2"));
+            assert!(!array.has_own_property(activation, "5"));
+            assert_eq!(array.get("5", activation).unwrap(), Value::Undefined);
+        })
+    }
+
+    #[test]
+    fn test_array_shrinks_capacity() {
+        with_object(0, |activation, _object| {
+            let array = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            );
+            array.set_length(activation.context.gc_context, 100_000);
+            assert!(array.array_capacity() >= 100_000);
+
+            for length in (10..100_000).rev() {
+                array.set_length(activation.context.gc_context, length);
+            }
+            assert_eq!(array.length(), 10);
+            assert!(array.array_capacity() < 100);
+        })
+    }
+
+    #[test]
+    fn test_array_element_watcher() {
+        with_object(0, |activation, _object| {
+            let array: Object<'_> = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            )
+            .into();
+            array.set("2", "old".into(), activation).unwrap();
+
+            let callback = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, this, args| {
+                    this.set("watched_old", args[1], activation)?;
+                    this.set("watched_new", args[2], activation)?;
+                    Ok(args[2])
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            array.set_watcher(activation, Cow::Borrowed("2"), callback, Value::Undefined);
+
+            array.set("2", "new".into(), activation).unwrap();
+
+            assert_eq!(array.get("watched_old", activation).unwrap(), "old".into());
+            assert_eq!(array.get("watched_new", activation).unwrap(), "new".into());
+            assert_eq!(array.array_element(2), "new".into());
+        })
+    }
+
+    #[test]
+    fn test_internal_watcher() {
+        with_object(0, |activation, object| {
+            let as_callback = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, this, args| {
+                    this.set("as_fired", true.into(), activation)?;
+                    Ok(args[2])
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let internal_callback = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, this, args| {
+                    this.set("internal_fired", true.into(), activation)?;
+                    Ok(args[2])
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let script_object = object.as_script_object().unwrap();
+            object.set_watcher(
+                activation,
+                Cow::Borrowed("x"),
+                as_callback,
+                Value::Undefined,
+            );
+            script_object.set_internal_watcher(
+                activation,
+                Cow::Borrowed("x"),
+                internal_callback,
+                Value::Undefined,
+            );
+
+            object.set("x", 1.into(), activation).unwrap();
+            assert_eq!(object.get("as_fired", activation).unwrap(), true.into());
+            assert_eq!(
+                object.get("internal_fired", activation).unwrap(),
+                true.into()
+            );
+
+            object.set("as_fired", false.into(), activation).unwrap();
+            object
+                .set("internal_fired", false.into(), activation)
+                .unwrap();
+            assert!(object.remove_watcher(activation, Cow::Borrowed("x")));
+
+            object.set("x", 2.into(), activation).unwrap();
+            assert_eq!(object.get("as_fired", activation).unwrap(), false.into());
+            assert_eq!(
+                object.get("internal_fired", activation).unwrap(),
+                true.into()
+            );
+            assert!(script_object.remove_internal_watcher(activation, Cow::Borrowed("x")));
+        })
+    }
+
+    #[test]
+    fn test_deep_equals() {
+        with_object(0, |activation, _object| {
+            fn graph<'gc>(activation: &mut Activation<'_, 'gc, '_>, last: f64) -> Object<'gc> {
+                let object_proto = activation.context.avm1.prototypes.object;
+                let array_proto = activation.context.avm1.prototypes.array;
+                let root =
+                    ScriptObject::object_cell(activation.context.gc_context, Some(object_proto));
+                let child =
+                    ScriptObject::object_cell(activation.context.gc_context, Some(object_proto));
+                let array: Object<'gc> =
+                    ScriptObject::array(activation.context.gc_context, Some(array_proto)).into();
+                array.set("0", 1.into(), activation).unwrap();
+                array.set("1", last.into(), activation).unwrap();
+                child.set("list", array.into(), activation).unwrap();
+                root.set("child", child.into(), activation).unwrap();
+                root.set("name", "root".into(), activation).unwrap();
+                root.set("self", root.into(), activation).unwrap();
+                root
+            }
+
+            let a = graph(activation, 2.0);
+            let b = graph(activation, 2.0);
+            let c = graph(activation, 3.0);
+
+            assert!(deep_equals(activation, a, b));
+            assert!(!deep_equals(activation, a, c));
+        })
+    }
+
+    #[test]
+    fn test_set_watcher_returns_previous() {
+        with_object(0, |activation, object| {
+            let first = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_avm, _this, args| Ok(args[2])),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let second = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_avm, _this, args| Ok(args[2])),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+
+            assert!(object
+                .set_watcher(activation, Cow::Borrowed("x"), first, Value::Undefined)
+                .is_none());
+            let previous = object
+                .set_watcher(activation, Cow::Borrowed("x"), second, Value::Undefined)
+                .unwrap();
+            assert!(Object::ptr_eq(previous.callback(), first));
+        })
+    }
+
+    #[test]
+    fn test_watcher_return_value_is_stored() {
+        with_object(0, |activation, object| {
+            // The user data selects what the watcher returns.
+            let watcher = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, _this, args| {
+                    let mode = args[3].coerce_to_string(activation)?;
+                    Ok(match mode.as_str() {
+                        "old" => args[1],
+                        "modified" => (args[2].coerce_to_f64(activation)? * 2.0).into(),
+                        _ => Value::Undefined,
+                    })
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            object.set("x", 1.into(), activation).unwrap();
+
+            object.set_watcher(activation, Cow::Borrowed("x"), watcher, "old".into());
+            object.set("x", 2.into(), activation).unwrap();
+            assert_eq!(object.get("x", activation).unwrap(), 1.into());
+
+            object.set_watcher(activation, Cow::Borrowed("x"), watcher, "modified".into());
+            object.set("x", 2.into(), activation).unwrap();
+            assert_eq!(object.get("x", activation).unwrap(), 4.into());
+
+            object.set_watcher(activation, Cow::Borrowed("x"), watcher, "undefined".into());
+            object.set("x", 2.into(), activation).unwrap();
+            assert_eq!(object.get("x", activation).unwrap(), Value::Undefined);
+            assert!(object.has_own_property(activation, "x"));
+        })
+    }
+
+    #[test]
+    fn test_watcher_reassigning_own_property() {
+        with_object(0, |activation, object| {
+            // Counts its calls, and writes the watched property back before returning.
+            let watcher = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, this, args| {
+                    let calls = this.get("calls", activation)?.coerce_to_i32(activation)?;
+                    this.set("calls", (calls + 1).into(), activation)?;
+                    let name = args[0].coerce_to_string(activation)?;
+                    this.set(&name, "reassigned".into(), activation)?;
+                    Ok(args[2])
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            object.set_watcher(activation, Cow::Borrowed("x"), watcher, Value::Undefined);
+
+            object.set("x", 1.into(), activation).unwrap();
+            assert_eq!(object.get("calls", activation).unwrap(), 1.into());
+            assert_eq!(object.get("x", activation).unwrap(), 1.into());
+
+            object.set("x", 2.into(), activation).unwrap();
+            assert_eq!(object.get("calls", activation).unwrap(), 2.into());
+            assert_eq!(object.get("x", activation).unwrap(), 2.into());
+        })
+    }
+
+    #[test]
+    fn test_clear_watchers() {
+        with_object(0, |activation, object| {
+            // Doubles every value it sees.
+            let watcher = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, _this, args| {
+                    Ok((args[2].coerce_to_f64(activation)? * 2.0).into())
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            object.set_watcher(activation, Cow::Borrowed("a"), watcher, Value::Undefined);
+            object.set_watcher(activation, Cow::Borrowed("b"), watcher, Value::Undefined);
+            object.set("a", 1.into(), activation).unwrap();
+            assert_eq!(object.get("a", activation).unwrap(), 2.into());
+
+            object.clear_watchers(activation.context.gc_context);
+            object.set("a", 1.into(), activation).unwrap();
+            object.set("b", 1.into(), activation).unwrap();
+            assert_eq!(object.get("a", activation).unwrap(), 1.into());
+            assert_eq!(object.get("b", activation).unwrap(), 1.into());
+        })
+    }
+
+    #[test]
+    fn test_array_index_beyond_uint32() {
+        with_object(0, |activation, _object| {
+            let array = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            );
+            array.set("1", "one".into(), activation).unwrap();
+            assert_eq!(array.length(), 2);
+
+            for &name in &["4294967295", "5000000000", "18446744073709551616"] {
+                array.set(name, "big".into(), activation).unwrap();
+                assert_eq!(array.get(name, activation).unwrap(), "big".into());
+                assert!(array.has_own_property(activation, name));
+            }
+            assert_eq!(array.length(), 2);
+            assert_eq!(array.get("length", activation).unwrap(), 2.into());
+
+            assert!(array.delete(activation, "5000000000"));
+            assert_eq!(
+                array.get("5000000000", activation).unwrap(),
+                Value::Undefined
+            );
+            assert_eq!(array.array_element(1), "one".into());
+        })
+    }
+
+    #[test]
+    fn test_array_length_cannot_be_deleted() {
+        with_object(0, |activation, _object| {
+            let array = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            );
+            array.set_array_elements(0, &[1.into(), 2.into()], activation.context.gc_context);
+
+            assert!(!array.delete(activation, "length"));
+            assert!(!array.is_property_enumerable(activation, "length"));
+            assert_eq!(array.length(), 2);
+            assert_eq!(array.get("length", activation).unwrap(), 2.into());
+
+            // Elements can still be deleted.
+            assert!(array.delete(activation, "0"));
+            assert_eq!(array.get("length", activation).unwrap(), 2.into());
+        })
+    }
+
+    #[test]
+    fn test_restore_properties() {
+        with_object(0, |activation, object| {
+            let object = object.as_script_object().unwrap();
+            let getter = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_avm, _this, _args| Ok("Virtual!".into())),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            object.define_value(
+                activation.context.gc_context,
+                "a",
+                1.into(),
+                Attribute::empty(),
+            );
+            object.define_value(
+                activation.context.gc_context,
+                "b",
+                "two".into(),
+                Attribute::DONT_ENUM | Attribute::READ_ONLY,
+            );
+            object.add_property(
+                activation.context.gc_context,
+                "virtual",
+                getter,
+                None,
+                Attribute::empty(),
+            );
+            let snapshot = object.properties_snapshot();
+            assert!(snapshot.iter().any(|(name, value, _)| name == "virtual"
+                && matches!(value, SnapshotValue::Virtual { set: None, .. })));
+
+            object.set("a", 10.into(), activation).unwrap();
+            object.set("c", 3.into(), activation).unwrap();
+            object.delete(activation, "b");
+            object.delete(activation, "virtual");
+            object.restore_properties(activation.context.gc_context, &snapshot);
+
+            assert_eq!(object.properties_snapshot(), snapshot);
+            assert_eq!(object.get("a", activation).unwrap(), 1.into());
+            assert_eq!(object.get("b", activation).unwrap(), "two".into());
+            assert_eq!(object.get("c", activation).unwrap(), Value::Undefined);
+            assert_eq!(
+                object.get("virtual", activation).unwrap(),
+                "Virtual!".into()
+            );
+            assert!(!object.is_property_enumerable(activation, "b"));
+            object.set("b", "changed".into(), activation).unwrap();
+            assert_eq!(object.get("b", activation).unwrap(), "two".into());
+
+            // Array elements are rebuilt from the restored properties.
+            let array = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            );
+            array.set_array_elements(0, &[1.into(), 2.into()], activation.context.gc_context);
+            let snapshot = array.properties_snapshot();
+            array.set_array_element(2, 3.into(), activation.context.gc_context);
+            array.restore_properties(activation.context.gc_context, &snapshot);
+            assert_eq!(array.length(), 2);
+            let elements: Vec<Value> = vec![1.into(), 2.into()];
+            assert_eq!(array.array(), elements);
+        })
+    }
+
+    #[test]
+    fn test_is_array_backed() {
+        with_object(0, |activation, object| {
+            let array = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            );
+            assert!(array.is_array_backed());
+
+            let object = object.as_script_object().unwrap();
+            assert!(!object.is_array_backed());
+            object.promote_to_array(activation.context.gc_context);
+            assert!(object.is_array_backed());
+        })
+    }
+
+    #[test]
+    fn test_set_interfaces_ignores_non_functions() {
+        with_object(0, |activation, object| {
+            let function = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok(Value::Undefined)),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let plain = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            )
+            .into();
+
+            object.set_interfaces(
+                activation.context.gc_context,
+                vec![plain, function, plain, function],
+            );
+            let interfaces = object.interfaces();
+            assert_eq!(interfaces.len(), 2);
+            assert!(interfaces
+                .iter()
+                .all(|iface| Object::ptr_eq(*iface, function)));
+        })
+    }
+
+    #[test]
+    fn test_add_interface() {
+        with_object(7, |activation, _object| {
+            let iface = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok(Value::Undefined)),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let iface_proto = iface.get("prototype", activation).unwrap();
+            let iface_proto = iface_proto.coerce_to_object(activation);
+            let class_proto: Object<'_> = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            )
+            .into();
+            let instance = ScriptObject::object(activation.context.gc_context, Some(class_proto));
+            assert!(!instance
+                .is_instance_of(activation, iface, iface_proto)
+                .unwrap());
+
+            // The interface is added after the instance was constructed.
+            class_proto.add_interface(activation.context.gc_context, iface);
+            class_proto.add_interface(activation.context.gc_context, iface);
+            assert_eq!(class_proto.interfaces().len(), 1);
+            assert!(instance
+                .is_instance_of(activation, iface, iface_proto)
+                .unwrap());
+        })
+    }
+
+    #[test]
+    fn test_stored_proto_property() {
+        with_object(0, |activation, object| {
+            let proto = object.proto();
+            assert!(object.has_own_property(activation, "__proto__"));
+            assert_eq!(object.get("__proto__", activation).unwrap(), proto);
+
+            object.as_script_object().unwrap().define_value(
+                activation.context.gc_context,
+                "__proto__",
+                "stored".into(),
+                Attribute::empty(),
+            );
+            assert!(object.has_own_property(activation, "__proto__"));
+            assert_eq!(
+                object.get("__proto__", activation).unwrap(),
+                "stored".into()
+            );
+            assert_eq!(object.proto(), proto);
+        })
+    }
+
+    #[test]
+    fn test_get_keys_order() {
+        with_object(0, |activation, object| {
+            let proto = object.proto().coerce_to_object(activation);
+            proto.set("inherited", Value::Null, activation).unwrap();
+            for name in &["c", "a", "b"] {
+                object.set(name, Value::Null, activation).unwrap();
+            }
+
+            assert_eq!(
+                object.get_keys(activation),
+                vec!["inherited", "b", "a", "c"]
+            );
+        })
+    }
+
+    #[test]
+    fn test_array_delete_and_length() {
+        with_object(0, |activation, _object| {
+            let array: Object<'_> = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            )
+            .into();
+            for i in 0..5 {
+                array.set(&i.to_string(), i.into(), activation).unwrap();
+            }
+
+            assert!(array.delete(activation, "2"));
+            assert_eq!(array.length(), 5);
+            assert_eq!(array.get("length", activation).unwrap(), 5.into());
+            assert_eq!(array.get("2", activation).unwrap(), Value::Undefined);
+            assert_eq!(array.array_element(2), Value::Undefined);
+
+            array.set("length", 2.into(), activation).unwrap();
+            assert_eq!(array.length(), 2);
+            assert_eq!(array.array().len(), 2);
+            assert_eq!(array.get("3", activation).unwrap(), Value::Undefined);
+            assert_eq!(array.get("1", activation).unwrap(), 1.into());
+        })
+    }
+
+    #[test]
+    fn test_array_delete_element_not_enumerated() {
+        with_object(0, |activation, _object| {
+            let array: Object<'_> = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            )
+            .into();
+            for i in 0..3 {
+                array.set_array_element(i, i.into(), activation.context.gc_context);
+            }
+
+            array.delete_array_element(1, activation.context.gc_context);
+            assert_eq!(array.length(), 3);
+            assert_eq!(array.array_element(1), Value::Undefined);
+
+            let keys = array.get_keys(activation);
+            assert!(keys.contains(&"0".to_string()));
+            assert!(!keys.contains(&"1".to_string()));
+            assert!(keys.contains(&"2".to_string()));
+            assert!(!keys.contains(&"length".to_string()));
+        })
+    }
+
+    #[test]
+    fn test_get_resolve() {
+        with_object(0, |activation, object| {
+            let resolve = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, _this, args| {
+                    let name = args[0].coerce_to_string(activation)?;
+                    Ok(
+                        AvmString::new(activation.context.gc_context, format!("resolved {}", name))
+                            .into(),
+                    )
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            let proto = ScriptObject::object(activation.context.gc_context, None);
+            proto.set("__resolve", resolve.into(), activation).unwrap();
+            object.set_proto(activation.context.gc_context, proto.into());
+            object.set("present", "value".into(), activation).unwrap();
+
+            assert_eq!(object.get("present", activation).unwrap(), "value".into());
+            assert_eq!(
+                object.get("missing", activation).unwrap(),
+                "resolved missing".into()
+            );
+            assert_eq!(object.get("__resolve", activation).unwrap(), resolve.into());
+
+            // A non-function `__resolve` is ignored.
+            object.set("__resolve", 5.into(), activation).unwrap();
+            assert_eq!(object.get("missing", activation).unwrap(), Value::Undefined);
+        })
+    }
+
+    #[test]
+    fn test_array_set_length_coerces() {
+        with_object(0, |activation, _object| {
+            let array: Object<'_> = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            )
+            .into();
+            fn reset<'gc>(array: Object<'gc>, activation: &mut Activation<'_, 'gc, '_>) {
+                for i in 0..5 {
+                    array.set_array_element(i, i.into(), activation.context.gc_context);
+                }
+            }
+
+            reset(array, activation);
+            array.set("length", "3".into(), activation).unwrap();
+            assert_eq!(array.length(), 3);
+            assert_eq!(array.get("length", activation).unwrap(), 3.into());
+            assert_eq!(array.get("3", activation).unwrap(), Value::Undefined);
+            assert_eq!(array.get("2", activation).unwrap(), 2.into());
+
+            reset(array, activation);
+            array.set("length", f64::NAN.into(), activation).unwrap();
+            assert_eq!(array.length(), 0);
+            assert_eq!(array.get("length", activation).unwrap(), 0.into());
+            assert_eq!(array.get("0", activation).unwrap(), Value::Undefined);
+
+            reset(array, activation);
+            array.set("length", 2.9.into(), activation).unwrap();
+            assert_eq!(array.length(), 2);
+            assert_eq!(array.get("length", activation).unwrap(), 2.into());
+            assert_eq!(array.get("1", activation).unwrap(), 1.into());
+            assert_eq!(array.get("2", activation).unwrap(), Value::Undefined);
+
+            // Negative lengths wrap around rather than being made positive.
+            let array: Object<'_> = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            )
+            .into();
+            reset(array, activation);
+            array.set("length", (-1).into(), activation).unwrap();
+            assert_eq!(array.length(), 0xFFFF_FFFF);
+            assert_eq!(
+                array.get("length", activation).unwrap(),
+                0xFFFF_FFFFu32.into()
+            );
+            assert_eq!(array.get("1", activation).unwrap(), 1.into());
+        })
+    }
+
+    #[test]
+    fn test_plain_object_length_is_ordinary() {
+        with_object(0, |activation, object| {
+            // The raw value is stored, but the length is still tracked for array methods.
+            object.set("length", "5".into(), activation).unwrap();
+            assert_eq!(object.get("length", activation).unwrap(), "5".into());
+            assert_eq!(object.length(), 5);
+            assert!(object.get_keys(activation).contains(&"length".to_string()));
+
+            assert!(object.delete(activation, "length"));
+            assert!(!object.has_own_property(activation, "length"));
+        })
+    }
+
+    #[test]
+    fn test_define_values() {
+        with_object(0, |activation, object| {
+            let names: Vec<String> = (0..100).map(|i| format!("prop{}", i)).collect();
+            let entries: Vec<_> = names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| (name.as_str(), i.into(), Attribute::empty()))
+                .collect();
+            let object = object.as_script_object().unwrap();
+            object.define_values(activation.context.gc_context, &entries, true);
+
+            for (i, name) in names.iter().enumerate() {
+                assert_eq!(object.get(name, activation).unwrap(), i.into());
+            }
+            assert_eq!(object.get_keys(activation).len(), 100);
+        })
+    }
+
+    #[test]
+    fn test_define_values_case_insensitive() {
+        with_object(6, |activation, _object| {
+            let gc_context = activation.context.gc_context;
+            let entries = [
+                ("a", 1.into(), Attribute::empty()),
+                ("A", 2.into(), Attribute::empty()),
+            ];
+
+            let object = ScriptObject::object(gc_context, None);
+            object.define_values(gc_context, &entries, false);
+            assert_eq!(object.get_keys(activation), vec!["a".to_string()]);
+            assert_eq!(object.get("a", activation).unwrap(), 2.into());
+
+            let object = ScriptObject::object(gc_context, None);
+            object.define_values(gc_context, &entries, true);
+            assert_eq!(object.get_keys(activation).len(), 2);
+        })
+    }
+
+    #[test]
+    fn test_with_capacity_matches_default() {
+        with_object(0, |activation, _object| {
+            let proto = Some(activation.context.avm1.prototypes.array);
+            let array = ScriptObject::array(activation.context.gc_context, proto);
+            let presized =
+                ScriptObject::array_with_capacity(activation.context.gc_context, proto, 3);
+            assert_eq!(presized.length(), 0);
+            assert_eq!(presized.get("length", activation).unwrap(), 0.into());
+            for i in 0..3 {
+                array.set_array_element(i, i.into(), activation.context.gc_context);
+                presized.set_array_element(i, i.into(), activation.context.gc_context);
+            }
+            assert_eq!(presized.array(), array.array());
+            assert_eq!(presized.get_keys(activation), array.get_keys(activation));
+
+            let proto = Some(activation.context.avm1.prototypes.object);
+            let object = ScriptObject::object(activation.context.gc_context, proto);
+            let presized =
+                ScriptObject::object_with_capacity(activation.context.gc_context, proto, 3);
+            for name in &["a", "b", "c"] {
+                object.set(name, 1.into(), activation).unwrap();
+                presized.set(name, 1.into(), activation).unwrap();
+            }
+            assert_eq!(presized.get_keys(activation), object.get_keys(activation));
+        })
+    }
+
+    #[test]
+    fn test_properties_array_element_many() {
+        with_object(0, |activation, object| {
+            const LENGTH: usize = 100_000;
+            object.set_length(activation.context.gc_context, LENGTH);
+            for i in (0..LENGTH).step_by(2) {
+                object.define_value(
+                    activation.context.gc_context,
+                    &i.to_string(),
+                    i.into(),
+                    Attribute::empty(),
+                );
+            }
+            object.define_value(
+                activation.context.gc_context,
+                &LENGTH.to_string(),
+                LENGTH.into(),
+                Attribute::empty(),
+            );
+
+            for i in 0..LENGTH {
+                let expected = if i % 2 == 0 {
+                    i.into()
+                } else {
+                    Value::Undefined
+                };
+                assert_eq!(object.array_element(i), expected);
+            }
+            // Elements past the length are not visible, even if the property exists.
+            assert_eq!(object.array_element(LENGTH), Value::Undefined);
+        })
+    }
+
+    #[test]
+    fn test_array_keys_in_index_order() {
+        with_object(0, |activation, _object| {
+            let gc_context = activation.context.gc_context;
+            let array = ScriptObject::array(gc_context, None);
+            array.set_array_element(2, "c".into(), gc_context);
+            array.set("name", "named".into(), activation).unwrap();
+            array.set_array_element(0, "a".into(), gc_context);
+            array.set_array_element(10, "k".into(), gc_context);
+            array.set_array_element(1, "b".into(), gc_context);
+
+            assert_eq!(
+                array.get_keys(activation),
+                vec!["0", "1", "2", "10", "name"]
+            );
+        })
+    }
+
+    #[test]
+    fn test_prototype_cycles_are_rejected() {
+        with_object(0, |activation, object| {
+            let gc_context = activation.context.gc_context;
+            let original_proto = object.proto();
+            let a = ScriptObject::object(gc_context, None);
+            let b = ScriptObject::object(gc_context, Some(a.into()));
+            object
+                .set("__proto__", Value::Object(b.into()), activation)
+                .unwrap();
+
+            // Each of these would close a loop back to the object being assigned to.
+            a.set("__proto__", object.into(), activation).unwrap();
+            assert_eq!(a.proto(), Value::Undefined);
+            a.set("__proto__", Value::Object(a.into()), activation)
+                .unwrap();
+            assert_eq!(a.proto(), Value::Undefined);
+            a.set_proto(gc_context, Value::Object(b.into()));
+            assert_eq!(a.proto(), Value::Undefined);
+
+            // Lookups still terminate.
+            assert!(!object.has_property(activation, "missing"));
+            assert_eq!(object.get("missing", activation).unwrap(), Value::Undefined);
+            assert!(object.get_keys(activation).is_empty());
+
+            // Assignments that don't form a cycle still work.
+            a.set("__proto__", original_proto, activation).unwrap();
+            assert_eq!(a.proto(), original_proto);
+            assert!(object.has_property(activation, "toString"));
+        })
+    }
+
+    #[test]
+    fn test_huge_array_length() {
+        with_object(0, |activation, _object| {
+            let gc_context = activation.context.gc_context;
+            let array = ScriptObject::array(gc_context, None);
+            array.set_array_elements(0, &[1.into(), 2.into()], gc_context);
+
+            // This would need tens of gigabytes as a dense vector.
+            array
+                .set("length", 0xFFFF_FFFFu32.into(), activation)
+                .unwrap();
+            assert_eq!(array.length(), 0xFFFF_FFFF);
+            assert_eq!(
+                array.get("length", activation).unwrap(),
+                0xFFFF_FFFFu32.into()
+            );
+            assert!(array.is_array_backed());
+            assert!(array.array_capacity() < MAX_DENSE_ARRAY_LENGTH);
+            assert_eq!(array.array_element(1), 2.into());
+
+            array.set_length(gc_context, 1);
+            assert_eq!(array.get("length", activation).unwrap(), 1.into());
+            assert_eq!(array.array_element(0), 1.into());
+            assert_eq!(array.array_element(1), Value::Undefined);
+            assert!(!array.has_own_property(activation, "1"));
+            assert!(array.is_array_backed());
+
+            // Short enough again to be stored densely.
+            array.set_array_element(2, 3.into(), gc_context);
+            assert_eq!(array.length(), 3);
+            assert!(array.array_capacity() >= 3);
+        })
+    }
+
+    #[test]
+    fn test_array_element_present() {
+        with_object(0, |activation, _object| {
+            let gc_context = activation.context.gc_context;
+            let proto = ScriptObject::object(gc_context, None);
+            proto.define_value(gc_context, "4", "inherited".into(), Attribute::empty());
+            let array = ScriptObject::array(gc_context, Some(proto.into()));
+            array.set_array_elements(0, &[1.into(), Value::Undefined, 3.into()], gc_context);
+            array.delete(activation, "2");
+            array.set_length(gc_context, 5);
+
+            // An explicit `undefined` is present, while deleted and padded slots are holes.
+            let present: Vec<_> = (0..6).map(|i| array.array_element_present(i)).collect();
+            assert_eq!(present, vec![true, true, false, false, false, false]);
+            assert_eq!(array.array_element(2), Value::Undefined);
+
+            array.set_array_element(2, 3.into(), gc_context);
+            assert!(array.array_element_present(2));
+        })
+    }
+
+    #[test]
+    fn test_clear() {
+        with_object(0, |activation, object| {
+            let gc_context = activation.context.gc_context;
+            let proto = ScriptObject::object(gc_context, None);
+            proto.define_value(gc_context, "inherited", 1.into(), Attribute::DONT_ENUM);
+            let array = ScriptObject::array(gc_context, Some(proto.into()));
+            array.set_array_elements(0, &[1.into(), 2.into(), 3.into()], gc_context);
+            array.set("name", "named".into(), activation).unwrap();
+            array.set_watcher(activation, Cow::Borrowed("x"), object, Value::Undefined);
+            array.seal(gc_context);
+
+            array.clear(gc_context);
+
+            // A cleared array is still an array, like a new one.
+            assert!(array.get_keys(activation).is_empty());
+            assert!(array.is_array_backed());
+            assert!(array.has_own_property(activation, "length"));
+            assert_eq!(array.get("length", activation).unwrap(), 0.into());
+            assert_eq!(array.length(), 0);
+            assert_eq!(array.array_element(0), Value::Undefined);
+            assert_eq!(array.get("name", activation).unwrap(), Value::Undefined);
+            assert!(!array.remove_watcher(activation, Cow::Borrowed("x")));
+            assert!(!array.is_sealed());
+            assert_eq!(array.proto(), Value::Object(proto.into()));
+            assert_eq!(array.get("inherited", activation).unwrap(), 1.into());
+            assert_eq!(array.type_of(), "object");
+
+            // The object can be filled again, and assigning `length` still resizes it.
+            array.set("x", 10.into(), activation).unwrap();
+            assert_eq!(array.get("x", activation).unwrap(), 10.into());
+            array.set("length", "2".into(), activation).unwrap();
+            assert_eq!(array.length(), 2);
+            assert_eq!(array.get("length", activation).unwrap(), 2.into());
+        })
+    }
+
+    #[test]
+    fn test_own_enumerable_keys() {
+        with_object(0, |activation, _object| {
+            let gc_context = activation.context.gc_context;
+            let proto = ScriptObject::object(gc_context, None);
+            proto.define_value(gc_context, "inherited", 1.into(), Attribute::empty());
+
+            let object = ScriptObject::object(gc_context, Some(proto.into()));
+            object.define_value(gc_context, "b", 2.into(), Attribute::empty());
+            object.define_value(gc_context, "hidden", 3.into(), Attribute::DONT_ENUM);
+            object.define_value(gc_context, "a", 4.into(), Attribute::empty());
+
+            assert_eq!(
+                object.own_enumerable_keys(activation),
+                vec!["b".to_string(), "a".to_string()]
+            );
+            assert!(object
+                .get_keys(activation)
+                .contains(&"inherited".to_string()));
+        })
+    }
+
+    #[test]
+    fn test_object_with_type() {
+        with_object(0, |activation, _object| {
+            let gc_context = activation.context.gc_context;
+            let proto = ScriptObject::object(gc_context, None);
+            let object =
+                ScriptObject::object_with_type(gc_context, Some(proto.into()), "movieclip");
+
+            assert_eq!(object.type_of(), "movieclip");
+            assert_eq!(object.proto(), Value::Object(proto.into()));
+            assert_eq!(
+                ScriptObject::object(gc_context, None).type_of(),
+                TYPE_OF_OBJECT
+            );
+        })
+    }
 }
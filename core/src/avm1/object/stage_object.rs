@@ -3,6 +3,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::Executable;
+use crate::avm1::object::script_object::{Watcher, TYPE_OF_OBJECT};
 use crate::avm1::property::Attribute;
 use crate::avm1::property_map::PropertyMap;
 use crate::avm1::{AvmString, Object, ObjectPtr, ScriptObject, TDisplayObject, TObject, Value};
@@ -45,12 +46,13 @@ impl<'gc> StageObject<'gc> {
         display_object: DisplayObject<'gc>,
         proto: Option<Object<'gc>>,
     ) -> Self {
-        let mut base = ScriptObject::object(gc_context, proto);
-
         // Movieclips have a special typeof "movieclip", while others are the default "object".
-        if display_object.as_movie_clip().is_some() {
-            base.set_type_of(gc_context, TYPE_OF_MOVIE_CLIP);
-        }
+        let type_of = if display_object.as_movie_clip().is_some() {
+            TYPE_OF_MOVIE_CLIP
+        } else {
+            TYPE_OF_OBJECT
+        };
+        let base = ScriptObject::object_with_type(gc_context, proto, type_of);
 
         Self(GcCell::allocate(
             gc_context,
@@ -313,7 +315,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         get: Object<'gc>,
         set: Option<Object<'gc>>,
         attributes: Attribute,
-    ) {
+    ) -> bool {
         self.0
             .read()
             .base
@@ -327,7 +329,7 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         get: Object<'gc>,
         set: Option<Object<'gc>>,
         attributes: Attribute,
-    ) {
+    ) -> bool {
         self.0
             .read()
             .base
@@ -340,17 +342,21 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         name: Cow<str>,
         callback: Object<'gc>,
         user_data: Value<'gc>,
-    ) {
+    ) -> Option<Watcher<'gc>> {
         self.0
             .read()
             .base
-            .set_watcher(activation, name, callback, user_data);
+            .set_watcher(activation, name, callback, user_data)
     }
 
     fn remove_watcher(&self, activation: &mut Activation<'_, 'gc, '_>, name: Cow<str>) -> bool {
         self.0.read().base.remove_watcher(activation, name)
     }
 
+    fn clear_watchers(&self, gc_context: MutationContext<'gc, '_>) {
+        self.0.read().base.clear_watchers(gc_context)
+    }
+
     fn has_property(&self, activation: &mut Activation<'_, 'gc, '_>, name: &str) -> bool {
         let obj = self.0.read();
         if obj.base.has_property(activation, name) {
@@ -414,6 +420,20 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         keys
     }
 
+    fn own_enumerable_keys(&self, activation: &mut Activation<'_, 'gc, '_>) -> Vec<String> {
+        let obj = self.0.read();
+        let mut keys = obj.base.own_enumerable_keys(activation);
+
+        if let Some(ctr) = obj.display_object.as_container() {
+            keys.extend(
+                ctr.iter_execution_list()
+                    .map(|child| child.name().to_string()),
+            );
+        }
+
+        keys
+    }
+
     fn length(&self) -> usize {
         self.0.read().base.length()
     }
@@ -899,16 +919,24 @@ fn quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _quality");
-    Ok("HIGH".into())
+    let quality = AvmString::new(
+        activation.context.gc_context,
+        activation.context.stage.quality().to_string(),
+    );
+    Ok(quality.into())
 }
 
 fn set_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _quality");
+    if let Ok(quality) = val.coerce_to_string(activation)?.parse() {
+        activation
+            .context
+            .stage
+            .set_quality(activation.context.gc_context, quality);
+    }
     Ok(())
 }
 
@@ -3,7 +3,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::Executable;
-use crate::avm1::object::script_object::TYPE_OF_OBJECT;
+use crate::avm1::object::script_object::{Watcher, TYPE_OF_OBJECT};
 use crate::avm1::object::search_prototype;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Object, ObjectPtr, ScriptObject, TObject, Value};
@@ -198,8 +198,9 @@ impl<'gc> TObject<'gc> for SuperObject<'gc> {
         _get: Object<'gc>,
         _set: Option<Object<'gc>>,
         _attributes: Attribute,
-    ) {
+    ) -> bool {
         //`super` cannot have properties defined on it
+        false
     }
 
     fn add_property_with_case(
@@ -209,8 +210,9 @@ impl<'gc> TObject<'gc> for SuperObject<'gc> {
         _get: Object<'gc>,
         _set: Option<Object<'gc>>,
         _attributes: Attribute,
-    ) {
+    ) -> bool {
         //`super` cannot have properties defined on it
+        false
     }
 
     fn set_watcher(
@@ -219,8 +221,9 @@ impl<'gc> TObject<'gc> for SuperObject<'gc> {
         _name: Cow<str>,
         _callback: Object<'gc>,
         _user_data: Value<'gc>,
-    ) {
+    ) -> Option<Watcher<'gc>> {
         //`super` cannot have properties defined on it
+        None
     }
 
     fn remove_watcher(&self, _activation: &mut Activation<'_, 'gc, '_>, _name: Cow<str>) -> bool {
@@ -228,6 +231,10 @@ impl<'gc> TObject<'gc> for SuperObject<'gc> {
         false
     }
 
+    fn clear_watchers(&self, _gc_context: MutationContext<'gc, '_>) {
+        //`super` cannot have properties defined on it
+    }
+
     fn has_property(&self, activation: &mut Activation<'_, 'gc, '_>, name: &str) -> bool {
         self.0.read().child.has_property(activation, name)
     }
@@ -248,6 +255,10 @@ impl<'gc> TObject<'gc> for SuperObject<'gc> {
         vec![]
     }
 
+    fn own_enumerable_keys(&self, _activation: &mut Activation<'_, 'gc, '_>) -> Vec<String> {
+        vec![]
+    }
+
     fn type_of(&self) -> &'static str {
         TYPE_OF_OBJECT
     }
@@ -2,6 +2,7 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::object::script_object::Watcher;
 use crate::avm1::object::{ObjectPtr, TObject};
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, ScriptObject, Value};
@@ -125,7 +126,7 @@ impl<'gc> TObject<'gc> for XmlAttributesObject<'gc> {
         get: Object<'gc>,
         set: Option<Object<'gc>>,
         attributes: Attribute,
-    ) {
+    ) -> bool {
         self.base()
             .add_property(gc_context, name, get, set, attributes)
     }
@@ -137,7 +138,7 @@ impl<'gc> TObject<'gc> for XmlAttributesObject<'gc> {
         get: Object<'gc>,
         set: Option<Object<'gc>>,
         attributes: Attribute,
-    ) {
+    ) -> bool {
         self.base()
             .add_property_with_case(activation, name, get, set, attributes)
     }
@@ -148,15 +149,19 @@ impl<'gc> TObject<'gc> for XmlAttributesObject<'gc> {
         name: Cow<str>,
         callback: Object<'gc>,
         user_data: Value<'gc>,
-    ) {
+    ) -> Option<Watcher<'gc>> {
         self.base()
-            .set_watcher(activation, name, callback, user_data);
+            .set_watcher(activation, name, callback, user_data)
     }
 
     fn remove_watcher(&self, activation: &mut Activation<'_, 'gc, '_>, name: Cow<str>) -> bool {
         self.base().remove_watcher(activation, name)
     }
 
+    fn clear_watchers(&self, gc_context: MutationContext<'gc, '_>) {
+        self.base().clear_watchers(gc_context)
+    }
+
     fn define_value(
         &self,
         gc_context: MutationContext<'gc, '_>,
@@ -211,6 +216,12 @@ impl<'gc> TObject<'gc> for XmlAttributesObject<'gc> {
         base
     }
 
+    fn own_enumerable_keys(&self, activation: &mut Activation<'_, 'gc, '_>) -> Vec<String> {
+        let mut base = self.base().own_enumerable_keys(activation);
+        base.extend(self.node().attribute_keys());
+        base
+    }
+
     fn type_of(&self) -> &'static str {
         self.base().type_of()
     }
@@ -21,6 +21,14 @@ impl<V> PropertyMap<V> {
         Self(FnvIndexMap::default())
     }
 
+    /// Creates an empty map with room for at least `capacity` properties.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(FnvIndexMap::with_capacity_and_hasher(
+            capacity,
+            FnvBuildHasher::default(),
+        ))
+    }
+
     pub fn contains_key(&self, key: &str, case_sensitive: bool) -> bool {
         if case_sensitive {
             self.0.contains_key(&CaseSensitiveStr(key))
@@ -64,6 +72,14 @@ impl<V> PropertyMap<V> {
         }
     }
 
+    /// Gets the value for a numeric property, such as an array index.
+    ///
+    /// This avoids allocating a string for the key. Digits have no case, so this works
+    /// regardless of case sensitivity.
+    pub fn get_numeric(&self, key: usize) -> Option<&V> {
+        self.0.get(&CaseSensitiveStr(NumericKey::new(key).as_str()))
+    }
+
     /// Gets a mutable reference to the value for the specified property.
     #[allow(dead_code)]
     pub fn get_mut(&mut self, key: &str, case_sensitive: bool) -> Option<&mut V> {
@@ -79,6 +95,11 @@ impl<V> PropertyMap<V> {
         self.0.get_index(index).map(|(_, v)| v)
     }
 
+    /// Reserves capacity for at least `additional` more properties.
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+
     pub fn insert(&mut self, key: &str, value: V, case_sensitive: bool) -> Option<V> {
         match self.entry(key, case_sensitive) {
             Entry::Occupied(entry) => Some(entry.insert(value)),
@@ -153,6 +174,32 @@ impl<'a, V> VacantEntry<'a, V> {
     }
 }
 
+/// The decimal representation of a numeric key, formatted on the stack.
+struct NumericKey {
+    digits: [u8; 20],
+    start: usize,
+}
+
+impl NumericKey {
+    fn new(mut key: usize) -> Self {
+        let mut digits = [0; 20];
+        let mut start = digits.len();
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (key % 10) as u8;
+            key /= 10;
+            if key == 0 {
+                break;
+            }
+        }
+        Self { digits, start }
+    }
+
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.digits[self.start..]).unwrap()
+    }
+}
+
 /// Wraps a str, causing the hash map to use a case insensitive hash and equality.
 struct CaseInsensitiveStr<'a>(&'a str);
 
@@ -9,7 +9,7 @@ use crate::backend::log::NullLogBackend;
 use crate::backend::navigator::NullNavigatorBackend;
 use crate::backend::render::NullRenderer;
 use crate::backend::storage::MemoryStorageBackend;
-use crate::backend::ui::NullUiBackend;
+use crate::backend::ui::{NullUiBackend, UiBackend};
 use crate::backend::video::NullVideoBackend;
 use crate::context::ActionQueue;
 use crate::display_object::{MovieClip, Stage, TDisplayObject};
@@ -30,8 +30,20 @@ pub fn with_avm<F>(swf_version: u8, test: F)
 where
     F: for<'a, 'gc> FnOnce(&mut Activation<'_, 'gc, '_>, Object<'gc>) -> Result<(), Error<'gc>>,
 {
-    fn in_the_arena<'a, 'gc: 'a, F>(swf_version: u8, test: F, gc_context: MutationContext<'gc, '_>)
-    where
+    with_avm_and_ui(swf_version, &mut NullUiBackend::new(), test)
+}
+
+/// Like `with_avm`, but runs the test against the given UI backend.
+pub fn with_avm_and_ui<F>(swf_version: u8, ui: &mut dyn UiBackend, test: F)
+where
+    F: for<'a, 'gc> FnOnce(&mut Activation<'_, 'gc, '_>, Object<'gc>) -> Result<(), Error<'gc>>,
+{
+    fn in_the_arena<'a, 'gc: 'a, F>(
+        swf_version: u8,
+        ui: &mut dyn UiBackend,
+        test: F,
+        gc_context: MutationContext<'gc, '_>,
+    ) where
         F: FnOnce(&mut Activation<'_, 'gc, '_>, Object<'gc>) -> Result<(), Error<'gc>>,
     {
         let mut avm1 = Avm1::new(gc_context, swf_version);
@@ -51,7 +63,7 @@ where
             stage,
             rng: &mut SmallRng::from_seed([0u8; 32]),
             audio: &mut NullAudioBackend::new(),
-            ui: &mut NullUiBackend::new(),
+            ui,
             action_queue: &mut ActionQueue::new(),
             library: &mut Library::empty(gc_context),
             navigator: &mut NullNavigatorBackend::new(),
@@ -114,7 +126,7 @@ where
         run_test(&mut activation, root, test)
     }
 
-    rootless_arena(|gc_context| in_the_arena(swf_version, test, gc_context))
+    rootless_arena(|gc_context| in_the_arena(swf_version, ui, test, gc_context))
 }
 
 macro_rules! test_method {
@@ -1,6 +1,7 @@
 use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::test_utils::with_avm;
-use crate::avm1::TObject;
+use crate::avm1::{ScriptObject, TObject, Value};
 
 #[test]
 fn locals_into_form_values() {
@@ -23,3 +24,60 @@ fn locals_into_form_values() {
         Ok(())
     });
 }
+
+#[test]
+fn type_of_function_array_element() {
+    with_avm(19, |activation, _this| -> Result<(), Error> {
+        let array = ScriptObject::array(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.array),
+        );
+        let function = FunctionObject::function(
+            activation.context.gc_context,
+            Executable::Native(|_activation, _this, _args| Ok(Value::Undefined)),
+            None,
+            activation.context.avm1.prototypes.function,
+        );
+        array.set_array_element(0, function.into(), activation.context.gc_context);
+
+        let element = array.get("0", activation)?;
+        assert_eq!(element.type_of(), "function");
+        assert_eq!(Value::from(array).type_of(), "object");
+
+        Ok(())
+    });
+}
+
+#[test]
+fn constructed_instance_has_constructor() {
+    for &version in &[6, 7] {
+        with_avm(version, |activation, _this| -> Result<(), Error> {
+            let prototype = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            let constructor = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok(Value::Undefined)),
+                Some(activation.context.avm1.prototypes.function),
+                prototype.into(),
+            );
+
+            let instance = constructor
+                .construct(activation, &[])?
+                .coerce_to_object(activation);
+            assert_eq!(instance.get("constructor", activation)?, constructor.into());
+            // SWFv7 and later inherit `constructor` from the prototype.
+            assert_eq!(
+                instance.has_own_property(activation, "constructor"),
+                version < 7
+            );
+            assert!(!instance
+                .get_keys(activation)
+                .iter()
+                .any(|key| key == "constructor"));
+
+            Ok(())
+        });
+    }
+}
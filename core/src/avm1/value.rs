@@ -593,6 +593,45 @@ mod test {
         });
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn object_to_number_uses_value_of() {
+        with_avm(7, |activation, _this| -> Result<(), Error> {
+            let object = ScriptObject::object_cell(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+
+            // The default `valueOf` returns the object itself, which is not a number.
+            assert_eq!(
+                object.call_method("valueOf", &[], activation)?,
+                object.into()
+            );
+            assert!(Value::from(object).coerce_to_f64(activation)?.is_nan());
+
+            // `toString` is never consulted when coercing to a number.
+            let to_string = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok("7".into())),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            object.set("toString", to_string.into(), activation)?;
+            assert!(Value::from(object).coerce_to_f64(activation)?.is_nan());
+
+            let value_of = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok("5".into())),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            object.set("valueOf", value_of.into(), activation)?;
+            assert_eq!(Value::from(object).coerce_to_f64(activation)?, 5.0);
+
+            Ok(())
+        });
+    }
+
     #[test]
     #[allow(clippy::float_cmp)]
     fn to_number_swf6() {
@@ -51,7 +51,7 @@ pub use crate::avm2::names::{Namespace, QName};
 pub use crate::avm2::object::{ArrayObject, Object, ScriptObject, StageObject, TObject};
 pub use crate::avm2::value::Value;
 
-const BROADCAST_WHITELIST: [&str; 3] = ["enterFrame", "exitFrame", "frameConstructed"];
+const BROADCAST_WHITELIST: [&str; 4] = ["enterFrame", "exitFrame", "frameConstructed", "render"];
 
 /// Boxed error alias.
 ///
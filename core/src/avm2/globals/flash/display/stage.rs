@@ -257,26 +257,12 @@ pub fn align<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    let align = activation.context.stage.align();
-    let mut s = String::with_capacity(4);
-    // Match string values returned by AS.
     // It's possible to have an oxymoronic "TBLR".
     // This acts the same as "TL" (top-left takes priority).
-    // This order is different between AVM1 and AVM2!
-    use crate::display_object::StageAlign;
-    if align.contains(StageAlign::TOP) {
-        s.push('T');
-    }
-    if align.contains(StageAlign::BOTTOM) {
-        s.push('B');
-    }
-    if align.contains(StageAlign::LEFT) {
-        s.push('L');
-    }
-    if align.contains(StageAlign::RIGHT) {
-        s.push('R');
-    }
-    let align = AvmString::new(activation.context.gc_context, s);
+    let align = AvmString::new(
+        activation.context.gc_context,
+        activation.context.stage.align().to_string(),
+    );
     Ok(align.into())
 }
 
@@ -354,7 +340,7 @@ pub fn set_color<'gc>(
                 .coerce_to_u32(activation)?,
             255,
         );
-        dobj.set_background_color(activation.context.gc_context, Some(color));
+        dobj.set_background_color(&mut activation.context, Some(color));
     }
 
     Ok(Value::Undefined)
@@ -382,11 +368,38 @@ pub fn display_state<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    if activation.context.ui.is_fullscreen() {
-        Ok("fullScreenInteractive".into())
+    let stage = activation.context.stage;
+    stage.sync_display_state(&mut activation.context);
+    let display_state = AvmString::new(
+        activation.context.gc_context,
+        activation.context.stage.display_state().to_string(),
+    );
+    Ok(display_state.into())
+}
+
+/// Implement `displayState`'s setter
+pub fn set_display_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Ok(display_state) = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .parse()
+    {
+        activation
+            .context
+            .stage
+            .set_display_state(&mut activation.context, display_state);
     } else {
-        Ok("normal".into())
+        return Err(
+            "ArgumentError: Error #2008: Parameter displayState must be one of the accepted values."
+                .into(),
+        );
     }
+    Ok(Value::Undefined)
 }
 
 /// Implement `focus`'s getter
@@ -431,7 +444,11 @@ pub fn frame_rate<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Ok((*activation.context.frame_rate).into())
+    Ok(activation
+        .context
+        .stage
+        .frame_rate(&activation.context)
+        .into())
 }
 
 /// Implement `frameRate`'s setter
@@ -445,7 +462,10 @@ pub fn set_frame_rate<'gc>(
         .cloned()
         .unwrap_or(Value::Undefined)
         .coerce_to_number(activation)?;
-    *activation.context.frame_rate = new_frame_rate;
+    activation
+        .context
+        .stage
+        .set_frame_rate(&mut activation.context, new_frame_rate);
 
     Ok(Value::Undefined)
 }
@@ -471,6 +491,29 @@ pub fn set_show_default_context_menu<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implement `stageFocusRect`'s getter
+pub fn stage_focus_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.stage.stage_focus_rect().into())
+}
+
+/// Implement `stageFocusRect`'s setter
+pub fn set_stage_focus_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let stage_focus_rect = args.get(0).unwrap_or(&Value::Undefined).coerce_to_boolean();
+    activation
+        .context
+        .stage
+        .set_stage_focus_rect(activation.context.gc_context, stage_focus_rect);
+    Ok(Value::Undefined)
+}
+
 /// Implement `scaleMode`'s getter
 pub fn scale_mode<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -584,14 +627,51 @@ pub fn allows_full_screen_interactive<'gc>(
 }
 
 /// Implement `quality`'s getter
-///
-/// TODO: This is a stub.
 pub fn quality<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let quality = AvmString::new(
+        activation.context.gc_context,
+        activation.context.stage.quality().to_string(),
+    );
+    Ok(quality.into())
+}
+
+/// Implement `quality`'s setter
+pub fn set_quality<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Ok(quality) = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .parse()
+    {
+        activation
+            .context
+            .stage
+            .set_quality(activation.context.gc_context, quality);
+    } else {
+        return Err(
+            "ArgumentError: Error #2008: Parameter quality must be one of the accepted values."
+                .into(),
+        );
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implement `invalidate`
+pub fn invalidate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Ok("HIGH".into())
+    activation.context.stage.invalidate(&mut activation.context);
+    Ok(Value::Undefined)
 }
 
 /// Construct `Stage`'s class.
@@ -673,7 +753,7 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("browserZoomFactor", Some(browser_zoom_factor), None),
         ("color", Some(color), Some(set_color)),
         ("contentsScaleFactor", Some(contents_scale_factor), None),
-        ("displayState", Some(display_state), None),
+        ("displayState", Some(display_state), Some(set_display_state)),
         ("focus", Some(focus), Some(set_focus)),
         ("frameRate", Some(frame_rate), Some(set_frame_rate)),
         ("scaleMode", Some(scale_mode), Some(set_scale_mode)),
@@ -690,9 +770,17 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
             Some(allows_full_screen_interactive),
             None,
         ),
-        ("quality", Some(quality), None),
+        ("quality", Some(quality), Some(set_quality)),
+        (
+            "stageFocusRect",
+            Some(stage_focus_rect),
+            Some(set_stage_focus_rect),
+        ),
     ];
     write.define_public_builtin_instance_properties(PUBLIC_INSTANCE_PROPERTIES);
 
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethod)] = &[("invalidate", invalidate)];
+    write.define_public_builtin_instance_methods(PUBLIC_INSTANCE_METHODS);
+
     class
 }
@@ -1,3 +1,4 @@
+pub use crate::display_object::StageQuality;
 use crate::matrix::Matrix;
 use crate::shape_utils::DistilledShape;
 pub use crate::{library::MovieLibrary, transform::Transform, Color};
@@ -36,6 +37,12 @@ pub trait RenderBackend: Downcast {
         swf_tag: &swf::DefineBitsLossless,
     ) -> Result<BitmapInfo, Error>;
 
+    /// Sets the stage quality for the frames that follow.
+    ///
+    /// Backends may use this to adjust anti-aliasing and bitmap smoothing. By default, the
+    /// quality is ignored.
+    fn set_quality(&mut self, _quality: StageQuality) {}
+
     fn begin_frame(&mut self, clear: Color);
     fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool);
     fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform);
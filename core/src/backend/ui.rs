@@ -20,6 +20,9 @@ pub trait UiBackend: Downcast {
 
     fn is_fullscreen(&self) -> bool;
 
+    /// Requests the host to enter or leave fullscreen mode.
+    fn set_fullscreen(&mut self, is_full: bool);
+
     /// Displays a warning about unsupported content in Ruffle.
     /// The user can still click an "OK" or "run anyway" message to dismiss the warning.
     fn display_unsupported_message(&self);
@@ -85,6 +88,8 @@ impl UiBackend for NullUiBackend {
         false
     }
 
+    fn set_fullscreen(&mut self, _is_full: bool) {}
+
     fn display_unsupported_message(&self) {}
 
     fn message(&self, _message: &str) {}
@@ -14,7 +14,7 @@ use crate::backend::{
     video::VideoBackend,
 };
 use crate::context_menu::ContextMenuState;
-use crate::display_object::{EditText, MovieClip, SoundTransform, Stage};
+use crate::display_object::{EditText, MovieClip, SoundTransform, Stage, StageQuality};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
 use crate::library::Library;
@@ -387,6 +387,12 @@ pub struct RenderContext<'a, 'gc> {
     /// Whether to allow pushing a new mask. A masker-inside-a-masker does not work in Flash, instead
     /// causing the inner mask to be included as part of the outer mask. Maskee-inside-a-maskee works as one expects.
     pub allow_mask: bool,
+
+    /// The stage quality, which the renderer can use to adjust anti-aliasing and smoothing.
+    pub quality: StageQuality,
+
+    /// The focus tracker, used to highlight the focused object.
+    pub focus_tracker: FocusTracker<'gc>,
 }
 
 /// The type of action being run.
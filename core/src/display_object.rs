@@ -45,7 +45,7 @@ pub use edit_text::{AutoSizeMode, EditText, TextSelection};
 pub use graphic::Graphic;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
 pub use movie_clip::{MovieClip, Scene};
-pub use stage::{Stage, StageAlign, StageScaleMode};
+pub use stage::{Stage, StageAlign, StageDisplayState, StageQuality, StageScaleMode};
 pub use text::Text;
 pub use video::Video;
 
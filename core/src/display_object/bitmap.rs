@@ -114,7 +114,7 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext) {
-        if !self.world_bounds().intersects(&context.stage.view_bounds()) {
+        if !context.stage.is_visible(&self.world_bounds()) {
             // Off-screen; culled
             return;
         }
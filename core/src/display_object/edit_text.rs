@@ -1473,7 +1473,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
-        if !self.world_bounds().intersects(&context.stage.view_bounds()) {
+        if !context.stage.is_visible(&self.world_bounds()) {
             // Off-screen; culled
             return;
         }
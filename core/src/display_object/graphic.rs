@@ -165,7 +165,7 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext) {
-        if !self.world_bounds().intersects(&context.stage.view_bounds()) {
+        if !context.stage.is_visible(&self.world_bounds()) {
             // Off-screen; culled
             return;
         }
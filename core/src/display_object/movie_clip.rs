@@ -3168,10 +3168,14 @@ impl<'gc, 'a> MovieClip<'gc> {
         // Also note that a loaded child SWF could change background color only
         // if parent SWF is missing SetBackgroundColor tag.
         let background_color = reader.read_rgb()?;
-        if context.stage.background_color().is_none() {
+        if context.stage.movie_declared_background().is_none() {
             context
                 .stage
-                .set_background_color(context.gc_context, Some(background_color));
+                .set_movie_declared_background(context.gc_context, Some(background_color.clone()));
+        }
+        let stage = context.stage;
+        if stage.background_color().is_none() {
+            stage.set_background_color(context, Some(background_color));
         }
         Ok(())
     }
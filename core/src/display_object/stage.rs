@@ -1,5 +1,6 @@
 //! Root stage impl
 
+use crate::avm1::activation::Activation as Avm1Activation;
 use crate::avm1::Object as Avm1Object;
 use crate::avm2::{
     Activation as Avm2Activation, Event as Avm2Event, Namespace as Avm2Namespace,
@@ -9,17 +10,20 @@ use crate::avm2::{
 use crate::backend::ui::UiBackend;
 use crate::config::Letterbox;
 use crate::context::{RenderContext, UpdateContext};
+use crate::context_menu::ContextMenuState;
 use crate::display_object::container::{
     ChildContainer, DisplayObjectContainer, TDisplayObjectContainer,
 };
 use crate::display_object::{render_base, DisplayObject, DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
+use crate::tag_utils::SwfMovie;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmType, Instantiator};
 use bitflags::bitflags;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
+use swf::Rectangle;
 
 /// The Stage is the root of the display object hierarchy. It contains all AVM1
 /// levels as well as AVM2 movies.
@@ -48,9 +52,25 @@ pub struct StageData<'gc> {
     #[collect(require_static)]
     background_color: Option<Color>,
 
+    /// Whether the stage background may be transparent.
+    ///
+    /// This corresponds to the `transparent` window mode when embedded in a page. If this is
+    /// `false`, the alpha of the background color is ignored and the stage is always opaque.
+    transparent_background: bool,
+
+    /// The background color declared by the root movie's `SetBackgroundColor` tag.
+    ///
+    /// Unlike `background_color`, this is never overridden by the host.
+    #[collect(require_static)]
+    movie_declared_background: Option<Color>,
+
     /// Determines how player content is resized to fit the stage.
     letterbox: Letterbox,
 
+    /// The color of the bars drawn around letterboxed content.
+    #[collect(require_static)]
+    letterbox_color: Color,
+
     /// The dimensions of the SWF file.
     #[collect(require_static)]
     movie_size: (u32, u32),
@@ -65,6 +85,23 @@ pub struct StageData<'gc> {
     /// The alignment of the stage.
     align: StageAlign,
 
+    /// Whether or not the stage is currently fullscreen.
+    display_state: StageDisplayState,
+
+    /// Whether fullscreen was requested, but the UI backend hasn't reported entering it yet.
+    ///
+    /// Some backends, such as web, only enter fullscreen asynchronously.
+    fullscreen_pending: bool,
+
+    /// The rendering quality of the stage.
+    quality: StageQuality,
+
+    /// The area of the movie, in movie coordinates, to scale to fill the screen while fullscreen.
+    ///
+    /// If this is `None`, the whole movie is scaled according to the scale mode as usual.
+    #[collect(require_static)]
+    full_screen_source_rect: Option<Rectangle>,
+
     /// The dimensions of the stage's containing viewport.
     #[collect(require_static)]
     viewport_size: (u32, u32),
@@ -73,12 +110,47 @@ pub struct StageData<'gc> {
     /// to device-scale pixels.
     viewport_scale_factor: f64,
 
+    /// Space reserved around the edges of the viewport for host UI chrome, in
+    /// device pixels, ordered as top, right, bottom, left.
+    ///
+    /// The movie is scaled and positioned within the remaining area.
+    #[collect(require_static)]
+    chrome_insets: (f64, f64, f64, f64),
+
+    /// The offset of the content within the viewport, in device pixels.
+    ///
+    /// This allows hosts to pan around the movie.
+    #[collect(require_static)]
+    content_offset: (f64, f64),
+
     /// The bounds of the current viewport in twips, used for culling.
     view_bounds: BoundingBox,
 
+    /// The viewport area that rendering is clipped to, as the transform of a unit rectangle.
+    ///
+    /// This is `None` when content can't extend past the viewport, so no clip is needed.
+    #[collect(require_static)]
+    viewport_clip: Option<Matrix>,
+
     /// Whether to show default context menu items
     show_menu: bool,
 
+    /// Whether to draw a highlight around the focused object.
+    stage_focus_rect: bool,
+
+    /// Whether the mouse can interact with objects on the stage.
+    ///
+    /// Hosts can disable this while showing their own UI over the movie.
+    mouse_enabled: bool,
+
+    /// Whether the view matrix translation is rounded to whole device pixels.
+    ///
+    /// This keeps edges crisp in pixel-art content, which blurs when drawn at sub-pixel offsets.
+    pixel_snapping: bool,
+
+    /// Whether `invalidate` was called since the last render event.
+    invalidated: bool,
+
     /// The AVM2 view of this stage object.
     avm2_object: Avm2Object<'gc>,
 }
@@ -91,15 +163,29 @@ impl<'gc> Stage<'gc> {
                 base: Default::default(),
                 child: Default::default(),
                 background_color: None,
+                transparent_background: false,
+                movie_declared_background: None,
                 letterbox: Letterbox::Fullscreen,
+                letterbox_color: Color::from_rgb(0, 255),
                 movie_size: (width, height),
                 stage_size: (width, height),
                 scale_mode: Default::default(),
                 align: Default::default(),
+                display_state: Default::default(),
+                fullscreen_pending: false,
+                quality: Default::default(),
+                full_screen_source_rect: None,
                 viewport_size: (width, height),
                 viewport_scale_factor: 1.0,
+                chrome_insets: (0.0, 0.0, 0.0, 0.0),
+                content_offset: (0.0, 0.0),
                 view_bounds: Default::default(),
+                viewport_clip: None,
                 show_menu: true,
+                stage_focus_rect: true,
+                mouse_enabled: true,
+                pixel_snapping: false,
+                invalidated: false,
                 avm2_object: Avm2ScriptObject::bare_object(gc_context),
             },
         ));
@@ -111,8 +197,50 @@ impl<'gc> Stage<'gc> {
         self.0.read().background_color.clone()
     }
 
-    pub fn set_background_color(self, gc_context: MutationContext<'gc, '_>, color: Option<Color>) {
-        self.0.write(gc_context).background_color = color;
+    /// Set the stage background color, and request a render to show it.
+    pub fn set_background_color(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        color: Option<Color>,
+    ) {
+        self.0.write(context.gc_context).background_color = color;
+        *context.needs_render = true;
+    }
+
+    /// Get whether the stage background may be transparent.
+    pub fn transparent_background(self) -> bool {
+        self.0.read().transparent_background
+    }
+
+    /// Set whether the stage background may be transparent.
+    pub fn set_transparent_background(self, gc_context: MutationContext<'gc, '_>, value: bool) {
+        self.0.write(gc_context).transparent_background = value;
+    }
+
+    /// Get the background color declared by the movie, regardless of any host override.
+    pub fn movie_declared_background(self) -> Option<Color> {
+        self.0.read().movie_declared_background.clone()
+    }
+
+    pub fn set_movie_declared_background(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        color: Option<Color>,
+    ) {
+        self.0.write(gc_context).movie_declared_background = color;
+    }
+
+    /// Set the stage background from the `SetBackgroundColor` tag in the header of a new root movie.
+    ///
+    /// This is called by `set_level` whenever `_level0` is replaced, so that the background is
+    /// shown while the movie loads. A background color set by the host is left untouched.
+    fn set_background_color_from_tag(self, gc_context: MutationContext<'gc, '_>, movie: &SwfMovie) {
+        let color = movie.header().background_color();
+        let mut write = self.0.write(gc_context);
+        let previous = std::mem::replace(&mut write.movie_declared_background, color.clone());
+        if write.background_color.is_none() || write.background_color == previous {
+            write.background_color = color;
+        }
     }
 
     pub fn inverse_view_matrix(self) -> Matrix {
@@ -122,6 +250,27 @@ impl<'gc> Stage<'gc> {
         inverse_view_matrix
     }
 
+    /// Convert a position in the viewport, in device pixels, into stage pixels.
+    ///
+    /// The view matrix already accounts for `viewport_scale_factor`, so this is the inverse of
+    /// `stage_to_device` under every scale mode.
+    pub fn device_to_stage(self, x: f64, y: f64) -> (f64, f64) {
+        transform_point(&self.inverse_view_matrix(), x, y)
+    }
+
+    /// Convert a position in stage pixels into device pixels in the viewport.
+    pub fn stage_to_device(self, x: f64, y: f64) -> (f64, f64) {
+        transform_point(&self.matrix(), x, y)
+    }
+
+    /// Convert a position in the viewport, such as the mouse position, into movie coordinates.
+    ///
+    /// This is `device_to_stage` for positions measured in twips.
+    pub fn mouse_to_local(self, pos: (Twips, Twips)) -> (Twips, Twips) {
+        let (x, y) = self.device_to_stage(pos.0.to_pixels(), pos.1.to_pixels());
+        (Twips::from_pixels(x), Twips::from_pixels(y))
+    }
+
     pub fn letterbox(self) -> Letterbox {
         self.0.read().letterbox
     }
@@ -130,6 +279,16 @@ impl<'gc> Stage<'gc> {
         self.0.write(gc_context).letterbox = letterbox
     }
 
+    /// Get the color of the letterbox bars.
+    pub fn letterbox_color(self) -> Color {
+        self.0.read().letterbox_color.clone()
+    }
+
+    /// Set the color of the letterbox bars.
+    pub fn set_letterbox_color(self, gc_context: MutationContext<'gc, '_>, color: Color) {
+        self.0.write(gc_context).letterbox_color = color;
+    }
+
     /// Get the size of the SWF file.
     pub fn movie_size(self) -> (u32, u32) {
         self.0.read().movie_size
@@ -176,6 +335,90 @@ impl<'gc> Stage<'gc> {
         self.build_matrices(context);
     }
 
+    /// Get the stage display state.
+    pub fn display_state(self) -> StageDisplayState {
+        self.0.read().display_state
+    }
+
+    /// Set the stage display state.
+    /// This asks the UI backend to enter or leave fullscreen, and fires the fullscreen event
+    /// if the state changed.
+    pub fn set_display_state(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        display_state: StageDisplayState,
+    ) {
+        if self.display_state() == display_state {
+            return;
+        }
+
+        self.0.write(context.gc_context).display_state = display_state;
+        let is_full = display_state != StageDisplayState::Normal;
+        context.ui.set_fullscreen(is_full);
+        self.0.write(context.gc_context).fullscreen_pending =
+            is_full && !context.ui.is_fullscreen();
+        self.build_matrices(context);
+        self.fire_fullscreen_event(context, is_full);
+    }
+
+    /// Reconcile the display state with the UI backend.
+    ///
+    /// The user can leave fullscreen without the movie asking, e.g. by pressing Esc. If the
+    /// backend is no longer fullscreen, this returns to `StageDisplayState::Normal` and fires the
+    /// fullscreen event. A requested transition the backend hasn't completed yet is left alone.
+    pub fn sync_display_state(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if self.display_state() == StageDisplayState::Normal {
+            return;
+        }
+        if context.ui.is_fullscreen() {
+            self.0.write(context.gc_context).fullscreen_pending = false;
+            return;
+        }
+        if self.0.read().fullscreen_pending {
+            return;
+        }
+
+        self.0.write(context.gc_context).display_state = StageDisplayState::Normal;
+        self.build_matrices(context);
+        self.fire_fullscreen_event(context, false);
+    }
+
+    /// Get the stage rendering quality.
+    pub fn quality(self) -> StageQuality {
+        self.0.read().quality
+    }
+
+    /// Set the stage rendering quality.
+    pub fn set_quality(self, gc_context: MutationContext<'gc, '_>, quality: StageQuality) {
+        self.0.write(gc_context).quality = quality;
+    }
+
+    /// Get the area of the movie that is scaled to fill the screen while fullscreen.
+    pub fn full_screen_source_rect(self) -> Option<Rectangle> {
+        self.0.read().full_screen_source_rect.clone()
+    }
+
+    /// Set the area of the movie that is scaled to fill the screen while fullscreen.
+    pub fn set_full_screen_source_rect(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        rect: Option<Rectangle>,
+    ) {
+        self.0.write(context.gc_context).full_screen_source_rect = rect;
+        self.build_matrices(context);
+    }
+
+    /// Get the frame rate of the player, in frames per second.
+    pub fn frame_rate(self, context: &UpdateContext<'_, 'gc, '_>) -> f64 {
+        *context.frame_rate
+    }
+
+    /// Set the frame rate of the player, in frames per second.
+    /// This is clamped to the 0.01-1000 range allowed by Flash Player.
+    pub fn set_frame_rate(self, context: &mut UpdateContext<'_, 'gc, '_>, frame_rate: f64) {
+        *context.frame_rate = frame_rate.max(0.01).min(1000.0);
+    }
+
     /// Get the current viewport size, in device pixels.
     pub fn viewport_size(self) -> (u32, u32) {
         self.0.read().viewport_size
@@ -191,6 +434,9 @@ impl<'gc> Stage<'gc> {
     ///
     /// The width and height are in device pixels; while the `scale_factor`
     /// is the number of device pixels needed to make one standard scale pixel.
+    ///
+    /// A change in `scale_factor`, such as when the window moves to a display with a different
+    /// DPI, fires the resize event under every scale mode, so content can lay itself out again.
     pub fn set_viewport_size(
         self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -199,17 +445,85 @@ impl<'gc> Stage<'gc> {
         scale_factor: f64,
     ) {
         let mut write = self.0.write(context.gc_context);
+        let prev_stage_size = write.stage_size;
+        #[allow(clippy::float_cmp)]
+        let scale_factor_changed = write.viewport_scale_factor != scale_factor;
         write.viewport_size = (width, height);
         write.viewport_scale_factor = scale_factor;
         drop(write);
 
         self.build_matrices(context);
+
+        // `build_matrices` has already fired the event if the stage size changed. Hosts may set
+        // the initial scale factor before any movie is loaded, when there's nobody to notify.
+        if scale_factor_changed
+            && self.stage_size() == prev_stage_size
+            && self.child_by_depth(0).is_some()
+        {
+            self.fire_resize_event(context);
+        }
+    }
+
+    /// Get the current scale of the movie within the viewport, as separate X and Y factors.
+    ///
+    /// These only differ under `StageScaleMode::ExactFit`.
+    pub fn current_scale(self) -> (f64, f64) {
+        let view_matrix = self.matrix();
+        (f64::from(view_matrix.a), f64::from(view_matrix.d))
+    }
+
+    /// Get the space reserved for host UI chrome, in device pixels, as top, right, bottom, left.
+    pub fn chrome_insets(self) -> (f64, f64, f64, f64) {
+        self.0.read().chrome_insets
+    }
+
+    /// Set the space reserved for host UI chrome, in device pixels, as top, right, bottom, left.
+    pub fn set_chrome_insets(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        insets: (f64, f64, f64, f64),
+    ) {
+        self.0.write(context.gc_context).chrome_insets = insets;
+        self.build_matrices(context);
+    }
+
+    /// Get the offset of the content within the viewport, in device pixels.
+    pub fn content_offset(self) -> (f64, f64) {
+        self.0.read().content_offset
+    }
+
+    /// Set the offset of the content within the viewport, in device pixels.
+    pub fn set_content_offset(self, context: &mut UpdateContext<'_, 'gc, '_>, x: f64, y: f64) {
+        self.0.write(context.gc_context).content_offset = (x, y);
+        self.build_matrices(context);
     }
 
     pub fn view_bounds(self) -> BoundingBox {
         self.0.read().view_bounds.clone()
     }
 
+    /// Whether any part of `bounds`, in world coordinates, is within the visible stage area.
+    ///
+    /// Display objects that aren't visible are culled when rendering.
+    pub fn is_visible(self, bounds: &BoundingBox) -> bool {
+        self.0.read().view_bounds.intersects(bounds)
+    }
+
+    /// Get the portion of the movie currently visible in the viewport, in movie coordinates.
+    ///
+    /// Unlike `view_bounds`, this is never clamped to the letterboxed movie area.
+    pub fn visible_movie_rect(self) -> BoundingBox {
+        let (viewport_width, viewport_height) = self.0.read().viewport_size;
+        let viewport = BoundingBox {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels(viewport_width.into()),
+            y_max: Twips::from_pixels(viewport_height.into()),
+            valid: true,
+        };
+        viewport.transform(&self.inverse_view_matrix())
+    }
+
     pub fn show_menu(self) -> bool {
         self.0.read().show_menu
     }
@@ -219,6 +533,101 @@ impl<'gc> Stage<'gc> {
         write.show_menu = show_menu;
     }
 
+    /// Get whether a highlight is drawn around the focused object.
+    pub fn stage_focus_rect(self) -> bool {
+        self.0.read().stage_focus_rect
+    }
+
+    /// Set whether a highlight is drawn around the focused object.
+    pub fn set_stage_focus_rect(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        stage_focus_rect: bool,
+    ) {
+        self.0.write(gc_context).stage_focus_rect = stage_focus_rect;
+    }
+
+    /// Get whether the mouse can interact with objects on the stage.
+    pub fn mouse_enabled(self) -> bool {
+        self.0.read().mouse_enabled
+    }
+
+    /// Set whether the mouse can interact with objects on the stage.
+    ///
+    /// While disabled, no object is picked under the mouse, so no button or clip mouse events fire.
+    pub fn set_mouse_enabled(self, gc_context: MutationContext<'gc, '_>, mouse_enabled: bool) {
+        self.0.write(gc_context).mouse_enabled = mouse_enabled;
+    }
+
+    /// Get whether the view matrix translation is snapped to whole device pixels.
+    pub fn pixel_snapping(self) -> bool {
+        self.0.read().pixel_snapping
+    }
+
+    /// Set whether the view matrix translation is snapped to whole device pixels.
+    pub fn set_pixel_snapping(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        pixel_snapping: bool,
+    ) {
+        self.0.write(context.gc_context).pixel_snapping = pixel_snapping;
+        self.build_matrices(context);
+    }
+
+    /// Request a render event to be dispatched before the next render.
+    /// Implements AS3 `Stage.invalidate`.
+    pub fn invalidate(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.0.write(context.gc_context).invalidated = true;
+    }
+
+    /// Whether `invalidate` was called since the last render event.
+    pub fn invalidated(self) -> bool {
+        self.0.read().invalidated
+    }
+
+    /// Dispatches `Event.RENDER` to the display list if the stage was invalidated, and clears the
+    /// invalidation. Returns whether the stage was invalidated.
+    pub fn fire_render_event(self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        if !self.invalidated() {
+            return false;
+        }
+        self.0.write(context.gc_context).invalidated = false;
+
+        // `Event.RENDER` only exists in AVM2.
+        let library = context.library.library_for_movie_mut(context.swf.clone());
+        if library.avm_type() == AvmType::Avm2 {
+            let mut render_evt = Avm2Event::new("render");
+            render_evt.set_bubbles(false);
+            render_evt.set_cancelable(false);
+
+            let dobject_proto = context.avm2.prototypes().display_object;
+
+            if let Err(e) = crate::avm2::Avm2::broadcast_event(context, render_evt, dobject_proto) {
+                log::error!(
+                    "Encountered AVM2 error when broadcasting render event: {}",
+                    e
+                );
+            }
+        }
+        true
+    }
+
+    /// Build the context menu for this stage.
+    ///
+    /// The default items are only included if `show_menu` is set, and can be further hidden by
+    /// the content-provided `menu`. Custom items from `menu` are always included.
+    pub fn build_context_menu(
+        self,
+        activation: &mut Avm1Activation<'_, 'gc, '_>,
+        menu: Option<Avm1Object<'gc>>,
+    ) -> ContextMenuState<'gc> {
+        crate::avm1::globals::context_menu::make_context_menu_state(
+            menu,
+            self.show_menu(),
+            activation,
+        )
+    }
+
     /// Determine if we should letterbox the stage content.
     fn should_letterbox(self, ui: &mut dyn UiBackend) -> bool {
         // Only enable letterbox is the default `ShowAll` scale mode.
@@ -238,12 +647,19 @@ impl<'gc> Stage<'gc> {
         let align = stage.align;
         let prev_stage_size = stage.stage_size;
 
+        // The movie is laid out in the viewport area not covered by host UI chrome.
+        let (inset_top, inset_right, inset_bottom, inset_left) = stage.chrome_insets;
+        let viewport_width = (f64::from(stage.viewport_size.0) - inset_left - inset_right).max(0.0);
+        let viewport_height =
+            (f64::from(stage.viewport_size.1) - inset_top - inset_bottom).max(0.0);
+
         // Update stage size based on scale mode and DPI.
         stage.stage_size = if stage.scale_mode == StageScaleMode::NoScale {
             // Viewport size is adjusted for HiDPI.
-            let width = f64::from(stage.viewport_size.0) / stage.viewport_scale_factor;
-            let height = f64::from(stage.viewport_size.1) / stage.viewport_scale_factor;
-            (width.round() as u32, height.round() as u32)
+            (
+                no_scale_stage_dimension(viewport_width, stage.viewport_scale_factor),
+                no_scale_stage_dimension(viewport_height, stage.viewport_scale_factor),
+            )
         } else {
             stage.movie_size
         };
@@ -254,10 +670,6 @@ impl<'gc> Stage<'gc> {
         let movie_width = movie_width as f64;
         let movie_height = movie_height as f64;
 
-        let (viewport_width, viewport_height) = stage.viewport_size;
-        let viewport_width = viewport_width as f64;
-        let viewport_height = viewport_height as f64;
-
         let movie_aspect = movie_width / movie_height;
         let viewport_aspect = viewport_width / viewport_height;
 
@@ -292,21 +704,51 @@ impl<'gc> Stage<'gc> {
 
         let width_delta = viewport_width - movie_width * scale_x;
         let height_delta = viewport_height - movie_height * scale_y;
-        // The precedence is important here to match Flash behavior.
-        // L > R > "", T > B > "".
-        let tx = if align.contains(StageAlign::LEFT) {
-            0.0
-        } else if align.contains(StageAlign::RIGHT) {
-            width_delta
+        let (tx, ty) = align_offset(align, width_delta, height_delta);
+        let tx = tx + stage.content_offset.0;
+        let ty = ty + stage.content_offset.1;
+
+        // While fullscreen, a source rect overrides the scale mode: the rect is scaled to fit the
+        // viewport while keeping its aspect ratio, and centered.
+        let source_rect = match stage.display_state {
+            StageDisplayState::Normal => None,
+            _ => stage.full_screen_source_rect.clone(),
+        };
+        let (scale_x, scale_y, tx, ty) = if let Some(rect) = &source_rect {
+            let rect_x = rect.x_min.to_pixels();
+            let rect_y = rect.y_min.to_pixels();
+            let rect_width = (rect.x_max - rect.x_min).to_pixels();
+            let rect_height = (rect.y_max - rect.y_min).to_pixels();
+            let scale = (viewport_width / rect_width).min(viewport_height / rect_height);
+            let tx = (viewport_width - rect_width * scale) / 2.0 - rect_x * scale;
+            let ty = (viewport_height - rect_height * scale) / 2.0 - rect_y * scale;
+            (scale, scale, tx, ty)
         } else {
-            width_delta / 2.0
+            (scale_x, scale_y, tx, ty)
         };
-        let ty = if align.contains(StageAlign::TOP) {
-            0.0
-        } else if align.contains(StageAlign::BOTTOM) {
-            height_delta
+        let (matrix_tx, matrix_ty) = if stage.pixel_snapping {
+            ((tx + inset_left).round(), (ty + inset_top).round())
         } else {
-            height_delta / 2.0
+            (tx + inset_left, ty + inset_top)
+        };
+
+        // Content only spills out of the viewport area when it is cropped or offset, or when host
+        // UI chrome covers part of the viewport.
+        let can_overflow = matches!(
+            scale_mode,
+            StageScaleMode::NoBorder | StageScaleMode::NoScale
+        ) || stage.content_offset != (0.0, 0.0)
+            || stage.chrome_insets != (0.0, 0.0, 0.0, 0.0);
+        stage.viewport_clip = if can_overflow {
+            Some(Matrix::create_box(
+                viewport_width as f32,
+                viewport_height as f32,
+                0.0,
+                Twips::from_pixels(inset_left),
+                Twips::from_pixels(inset_top),
+            ))
+        } else {
+            None
         };
         drop(stage);
 
@@ -315,11 +757,14 @@ impl<'gc> Stage<'gc> {
             b: 0.0,
             c: 0.0,
             d: scale_y as f32,
-            tx: Twips::from_pixels(tx),
-            ty: Twips::from_pixels(ty),
+            tx: Twips::from_pixels(matrix_tx),
+            ty: Twips::from_pixels(matrix_ty),
         };
 
-        self.0.write(context.gc_context).view_bounds = if self.should_letterbox(context.ui) {
+        self.0.write(context.gc_context).view_bounds = if let Some(rect) = source_rect {
+            // Source rect: only the rect is shown.
+            rect.into()
+        } else if self.should_letterbox(context.ui) {
             // Letterbox: movie area
             BoundingBox {
                 x_min: Twips::ZERO,
@@ -343,20 +788,28 @@ impl<'gc> Stage<'gc> {
             }
         };
 
-        // Fire resize handler if stage size has changed.
-        if scale_mode == StageScaleMode::NoScale && stage_size_changed {
+        // Fire resize handler if the reported stage size has changed, regardless of scale mode.
+        if stage_size_changed {
             self.fire_resize_event(context);
         }
     }
 
+    /// The transform of a unit rectangle covering the viewport area that the movie is laid out
+    /// in, excluding any host UI chrome. Rendering is clipped to this area.
+    ///
+    /// Returns `None` if content can't extend past the viewport, in which case no clip is needed.
+    pub fn viewport_clip_matrix(self) -> Option<Matrix> {
+        self.0.read().viewport_clip
+    }
+
     /// Draw the stage's letterbox.
     fn draw_letterbox(&self, context: &mut RenderContext<'_, 'gc>) {
-        let black = Color::from_rgb(0, 255);
+        let letterbox_color = self.letterbox_color();
         let (viewport_width, viewport_height) = self.0.read().viewport_size;
         let viewport_width = viewport_width as f32;
         let viewport_height = viewport_height as f32;
 
-        let view_matrix = self.matrix();
+        let view_matrix = *self.matrix();
 
         let (movie_width, movie_height) = self.0.read().movie_size;
         let movie_width = movie_width as f32 * view_matrix.a;
@@ -372,7 +825,7 @@ impl<'gc> Stage<'gc> {
             // Top + bottom
             if margin_top > 0.0 {
                 context.renderer.draw_rect(
-                    black.clone(),
+                    letterbox_color.clone(),
                     &Matrix::create_box(
                         viewport_width,
                         margin_top,
@@ -384,7 +837,7 @@ impl<'gc> Stage<'gc> {
             }
             if margin_bottom > 0.0 {
                 context.renderer.draw_rect(
-                    black,
+                    letterbox_color,
                     &Matrix::create_box(
                         viewport_width,
                         margin_bottom,
@@ -398,7 +851,7 @@ impl<'gc> Stage<'gc> {
             // Left + right
             if margin_left > 0.0 {
                 context.renderer.draw_rect(
-                    black.clone(),
+                    letterbox_color.clone(),
                     &Matrix::create_box(
                         margin_left,
                         viewport_height,
@@ -410,7 +863,7 @@ impl<'gc> Stage<'gc> {
             }
             if margin_right > 0.0 {
                 context.renderer.draw_rect(
-                    black,
+                    letterbox_color,
                     &Matrix::create_box(
                         margin_right,
                         viewport_height,
@@ -431,6 +884,38 @@ impl<'gc> Stage<'gc> {
             .expect("Stage must always have a root movie")
     }
 
+    /// Get the movie loaded into `_level{level}`, if any.
+    pub fn get_level(self, level: u32) -> Option<DisplayObject<'gc>> {
+        self.child_by_depth(level as Depth)
+    }
+
+    /// Place `clip` as `_level{level}`.
+    ///
+    /// Any other movie already loaded into that level is removed from the stage. Replacing
+    /// `_level0` also resets the stage background to the one declared by the new movie.
+    pub fn set_level(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        level: u32,
+        clip: DisplayObject<'gc>,
+    ) {
+        let depth = level as Depth;
+        if let Some(old_clip) = self.child_by_depth(depth) {
+            if !DisplayObject::ptr_eq(old_clip, clip) {
+                let mut stage = self;
+                stage.remove_child(context, old_clip, Lists::all());
+            }
+        }
+        clip.set_depth(context.gc_context, depth);
+        self.replace_at_depth(context, clip, depth);
+
+        if level == 0 {
+            if let Some(movie) = clip.movie() {
+                self.set_background_color_from_tag(context.gc_context, &movie);
+            }
+        }
+    }
+
     /// Fires `Stage.onResize` in AVM1 or `Event.RESIZE` in AVM2.
     fn fire_resize_event(self, context: &mut UpdateContext<'_, 'gc, '_>) {
         // This event fires immediately when scaleMode is changed;
@@ -454,6 +939,28 @@ impl<'gc> Stage<'gc> {
             }
         }
     }
+
+    /// Fires `Stage.onFullScreen` in AVM1 or `FullScreenEvent.FULL_SCREEN` in AVM2.
+    fn fire_fullscreen_event(self, context: &mut UpdateContext<'_, 'gc, '_>, is_full: bool) {
+        let library = context.library.library_for_movie_mut(context.swf.clone());
+        if library.avm_type() == AvmType::Avm1 {
+            crate::avm1::Avm1::notify_system_listeners(
+                self.root_clip(),
+                context.swf.version(),
+                context,
+                "Stage",
+                "onFullScreen",
+                &[is_full.into()],
+            );
+        } else if let Avm2Value::Object(stage) = self.object2() {
+            let mut full_screen_event = Avm2Event::new("fullScreen");
+            full_screen_event.set_bubbles(false);
+            full_screen_event.set_cancelable(false);
+            if let Err(e) = crate::avm2::Avm2::dispatch_event(context, full_screen_event, stage) {
+                log::error!("Encountered AVM2 error when dispatching event: {}", e);
+            }
+        }
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
@@ -495,6 +1002,26 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
         self.0.write(context.gc_context).avm2_object = avm2_stage.into();
     }
 
+    fn mouse_pick(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        _self_node: DisplayObject<'gc>,
+        pos: (Twips, Twips),
+    ) -> Option<DisplayObject<'gc>> {
+        if !self.mouse_enabled() {
+            return None;
+        }
+
+        let levels: Vec<_> = self.iter_depth_list().collect();
+        for (_depth, level) in levels.iter().rev() {
+            let picked = level.mouse_pick(context, *level, pos);
+            if picked.is_some() {
+                return picked;
+            }
+        }
+        None
+    }
+
     fn id(&self) -> CharacterId {
         u16::MAX
     }
@@ -516,13 +1043,31 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
     }
 
     fn render(&self, context: &mut RenderContext<'_, 'gc>) {
-        let background_color = self
+        let mut background_color = self
             .background_color()
             .unwrap_or_else(|| Color::from_rgb(0xffffff, 255));
+        if !self.transparent_background() {
+            background_color.a = 255;
+        }
 
+        context.renderer.set_quality(context.quality);
         context.renderer.begin_frame(background_color);
 
+        // Clip content to the viewport, since some scale modes (such as `NoBorder`) overflow it.
+        let clip = self.viewport_clip_matrix();
+        if let Some(clip) = &clip {
+            context.renderer.push_mask();
+            context.renderer.draw_rect(Color::from_rgb(0, 255), clip);
+            context.renderer.activate_mask();
+        }
         render_base((*self).into(), context);
+        let focus_tracker = context.focus_tracker;
+        focus_tracker.render_highlight(context);
+        if let Some(clip) = &clip {
+            context.renderer.deactivate_mask();
+            context.renderer.draw_rect(Color::from_rgb(0, 255), clip);
+            context.renderer.pop_mask();
+        }
 
         if self.should_letterbox(context.ui) {
             self.draw_letterbox(context);
@@ -609,6 +1154,127 @@ impl FromStr for StageScaleMode {
     }
 }
 
+/// The display state of a stage.
+/// This controls whether the player is fullscreen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum StageDisplayState {
+    /// The player is displayed normally, embedded in its host.
+    /// This is the default display state.
+    Normal,
+
+    /// The player fills the entire screen, with keyboard input restricted.
+    FullScreen,
+
+    /// The player fills the entire screen and receives full keyboard input.
+    FullScreenInteractive,
+}
+
+impl Default for StageDisplayState {
+    fn default() -> StageDisplayState {
+        StageDisplayState::Normal
+    }
+}
+
+impl Display for StageDisplayState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Match string values returned by AS.
+        let s = match *self {
+            StageDisplayState::Normal => "normal",
+            StageDisplayState::FullScreen => "fullScreen",
+            StageDisplayState::FullScreenInteractive => "fullScreenInteractive",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for StageDisplayState {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let display_state = match s.to_ascii_lowercase().as_str() {
+            "normal" => StageDisplayState::Normal,
+            "fullscreen" => StageDisplayState::FullScreen,
+            "fullscreeninteractive" => StageDisplayState::FullScreenInteractive,
+            _ => return Err(ParseEnumError),
+        };
+        Ok(display_state)
+    }
+}
+
+/// The rendering quality of a stage.
+/// This controls how much anti-aliasing and bitmap smoothing the renderer applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum StageQuality {
+    /// No anti-aliasing, and bitmaps are never smoothed.
+    Low,
+
+    /// 2x anti-aliasing, and bitmaps are never smoothed.
+    Medium,
+
+    /// 4x anti-aliasing, and bitmaps are smoothed if the movie is static.
+    /// This is the default quality.
+    High,
+
+    /// 4x anti-aliasing, and bitmaps are always smoothed.
+    Best,
+
+    /// 8x8 anti-aliasing, and bitmaps are always smoothed.
+    High8x8,
+
+    /// 8x8 anti-aliasing in linear RGB space, and bitmaps are always smoothed.
+    High8x8Linear,
+
+    /// 16x16 anti-aliasing, and bitmaps are always smoothed.
+    High16x16,
+
+    /// 16x16 anti-aliasing in linear RGB space, and bitmaps are always smoothed.
+    High16x16Linear,
+}
+
+impl Default for StageQuality {
+    fn default() -> StageQuality {
+        StageQuality::High
+    }
+}
+
+impl Display for StageQuality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Match string values returned by AS.
+        let s = match *self {
+            StageQuality::Low => "LOW",
+            StageQuality::Medium => "MEDIUM",
+            StageQuality::High => "HIGH",
+            StageQuality::Best => "BEST",
+            StageQuality::High8x8 => "8X8",
+            StageQuality::High8x8Linear => "8X8LINEAR",
+            StageQuality::High16x16 => "16X16",
+            StageQuality::High16x16Linear => "16X16LINEAR",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for StageQuality {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let quality = match s.to_ascii_lowercase().as_str() {
+            "low" => StageQuality::Low,
+            "medium" => StageQuality::Medium,
+            "high" => StageQuality::High,
+            "best" => StageQuality::Best,
+            "8x8" => StageQuality::High8x8,
+            "8x8linear" => StageQuality::High8x8Linear,
+            "16x16" => StageQuality::High16x16,
+            "16x16linear" => StageQuality::High16x16Linear,
+            _ => return Err(ParseEnumError),
+        };
+        Ok(quality)
+    }
+}
+
 bitflags! {
     /// The alignment of the stage.
     /// This controls the position of the movie after scaling to fill the viewport.
@@ -633,6 +1299,25 @@ bitflags! {
     }
 }
 
+impl Display for StageAlign {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Match the canonical string values returned by AVM2.
+        // AVM1 orders these differently; see `avm1::globals::stage`.
+        let flags = [
+            (StageAlign::TOP, 'T'),
+            (StageAlign::BOTTOM, 'B'),
+            (StageAlign::LEFT, 'L'),
+            (StageAlign::RIGHT, 'R'),
+        ];
+        for &(flag, c) in &flags {
+            if self.contains(flag) {
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl FromStr for StageAlign {
     type Err = std::convert::Infallible;
 
@@ -652,3 +1337,1313 @@ impl FromStr for StageAlign {
         Ok(align)
     }
 }
+
+/// Convert a viewport dimension in device pixels to a stage dimension under `StageScaleMode::NoScale`.
+///
+/// This always rounds down, so that the stage never extends past the viewport, and a fractional
+/// scale factor (e.g. 1.25) maps each viewport size to a single, stable stage size. A small
+/// tolerance absorbs floating point error in the division, so that e.g. 330 / 1.1 gives 300.
+fn no_scale_stage_dimension(viewport: f64, scale_factor: f64) -> u32 {
+    const EPSILON: f64 = 1e-6;
+    (viewport / scale_factor + EPSILON).floor() as u32
+}
+
+/// Compute the offset of the movie within the viewport for the given alignment.
+///
+/// `width_delta` and `height_delta` are the space left over after scaling the movie, which is
+/// zero under `StageScaleMode::ExactFit`, making alignment a no-op in that mode.
+///
+/// The precedence is important here to match Flash behavior.
+/// L > R > "", T > B > "".
+fn align_offset(align: StageAlign, width_delta: f64, height_delta: f64) -> (f64, f64) {
+    let tx = if align.contains(StageAlign::LEFT) {
+        0.0
+    } else if align.contains(StageAlign::RIGHT) {
+        width_delta
+    } else {
+        width_delta / 2.0
+    };
+    let ty = if align.contains(StageAlign::TOP) {
+        0.0
+    } else if align.contains(StageAlign::BOTTOM) {
+        height_delta
+    } else {
+        height_delta / 2.0
+    };
+    (tx, ty)
+}
+
+/// Apply `matrix` to a point in pixels, without rounding to twips.
+fn transform_point(matrix: &Matrix, x: f64, y: f64) -> (f64, f64) {
+    let (a, b, c, d) = (
+        f64::from(matrix.a),
+        f64::from(matrix.b),
+        f64::from(matrix.c),
+        f64::from(matrix.d),
+    );
+    (
+        a * x + c * y + matrix.tx.to_pixels(),
+        b * x + d * y + matrix.ty.to_pixels(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::activation::Activation;
+    use crate::avm1::function::{Executable, FunctionObject};
+    use crate::avm1::test_utils::{with_avm, with_avm_and_ui};
+    use crate::avm1::{Error, ScriptObject, TObject, Value};
+    use crate::avm2::TObject as Avm2TObject;
+    use crate::backend::audio::NullAudioBackend;
+    use crate::backend::locale::NullLocaleBackend;
+    use crate::backend::log::LogBackend;
+    use crate::backend::navigator::NullNavigatorBackend;
+    use crate::backend::render::{
+        Bitmap, BitmapHandle, BitmapInfo, MovieLibrary, NullRenderer, RenderBackend, ShapeHandle,
+        Transform,
+    };
+    use crate::backend::storage::MemoryStorageBackend;
+    use crate::backend::ui::{MouseCursor, NullUiBackend};
+    use crate::backend::video::NullVideoBackend;
+    use crate::color_transform::ColorTransform;
+    use crate::display_object::MovieClip;
+    use crate::events::KeyCode;
+    use crate::focus_tracker::FocusTracker;
+    use crate::library::Library;
+    use crate::player::Player;
+    use crate::shape_utils::DistilledShape;
+    use crate::tag_utils::SwfSlice;
+    use crate::transform::TransformStack;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use swf::Fixed8;
+
+    /// A renderer that only records the quality it is given, the colors passed to `begin_frame`
+    /// and `draw_rect`, the color transforms that shapes are rendered with, and the rectangles
+    /// drawn as masks.
+    #[derive(Default)]
+    struct ColorRecorder {
+        quality: Option<StageQuality>,
+        clear_color: Option<Color>,
+        rect_colors: Vec<Color>,
+        shape_color_transforms: Vec<ColorTransform>,
+        mask_rects: Vec<Matrix>,
+        drawing_mask: bool,
+    }
+
+    impl RenderBackend for ColorRecorder {
+        fn set_viewport_dimensions(&mut self, _width: u32, _height: u32) {}
+        fn register_shape(
+            &mut self,
+            shape: DistilledShape,
+            library: Option<&MovieLibrary<'_>>,
+        ) -> ShapeHandle {
+            NullRenderer.register_shape(shape, library)
+        }
+        fn replace_shape(
+            &mut self,
+            _shape: DistilledShape,
+            _library: Option<&MovieLibrary<'_>>,
+            _handle: ShapeHandle,
+        ) {
+        }
+        fn register_glyph_shape(&mut self, shape: &swf::Glyph) -> ShapeHandle {
+            NullRenderer.register_glyph_shape(shape)
+        }
+        fn register_bitmap_jpeg(
+            &mut self,
+            data: &[u8],
+            jpeg_tables: Option<&[u8]>,
+        ) -> Result<BitmapInfo, Box<dyn std::error::Error>> {
+            NullRenderer.register_bitmap_jpeg(data, jpeg_tables)
+        }
+        fn register_bitmap_jpeg_2(
+            &mut self,
+            data: &[u8],
+        ) -> Result<BitmapInfo, Box<dyn std::error::Error>> {
+            NullRenderer.register_bitmap_jpeg_2(data)
+        }
+        fn register_bitmap_jpeg_3(
+            &mut self,
+            data: &[u8],
+            alpha_data: &[u8],
+        ) -> Result<BitmapInfo, Box<dyn std::error::Error>> {
+            NullRenderer.register_bitmap_jpeg_3(data, alpha_data)
+        }
+        fn register_bitmap_png(
+            &mut self,
+            swf_tag: &swf::DefineBitsLossless,
+        ) -> Result<BitmapInfo, Box<dyn std::error::Error>> {
+            NullRenderer.register_bitmap_png(swf_tag)
+        }
+        fn set_quality(&mut self, quality: StageQuality) {
+            self.quality = Some(quality);
+        }
+        fn begin_frame(&mut self, clear: Color) {
+            self.clear_color = Some(clear);
+        }
+        fn end_frame(&mut self) {}
+        fn render_bitmap(
+            &mut self,
+            _bitmap: BitmapHandle,
+            _transform: &Transform,
+            _smoothing: bool,
+        ) {
+        }
+        fn render_shape(&mut self, _shape: ShapeHandle, transform: &Transform) {
+            self.shape_color_transforms.push(transform.color_transform);
+        }
+        fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+            if self.drawing_mask {
+                self.mask_rects.push(*matrix);
+            } else {
+                self.rect_colors.push(color);
+            }
+        }
+        fn push_mask(&mut self) {
+            self.drawing_mask = true;
+        }
+        fn activate_mask(&mut self) {
+            self.drawing_mask = false;
+        }
+        fn deactivate_mask(&mut self) {
+            self.drawing_mask = true;
+        }
+        fn pop_mask(&mut self) {
+            self.drawing_mask = false;
+        }
+        fn get_bitmap_pixels(&mut self, _bitmap: BitmapHandle) -> Option<Bitmap> {
+            None
+        }
+        fn register_bitmap_raw(
+            &mut self,
+            width: u32,
+            height: u32,
+            rgba: Vec<u8>,
+        ) -> Result<BitmapHandle, Box<dyn std::error::Error>> {
+            NullRenderer.register_bitmap_raw(width, height, rgba)
+        }
+        fn update_texture(
+            &mut self,
+            bitmap: BitmapHandle,
+            width: u32,
+            height: u32,
+            rgba: Vec<u8>,
+        ) -> Result<BitmapHandle, Box<dyn std::error::Error>> {
+            NullRenderer.update_texture(bitmap, width, height, rgba)
+        }
+    }
+
+    /// Registers a `Stage` listener that counts `onResize` calls in its `count` property.
+    fn add_resize_listener<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Avm1Object<'gc>, Error<'gc>> {
+        let listener = ScriptObject::object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.object),
+        );
+        let on_resize = FunctionObject::function(
+            activation.context.gc_context,
+            Executable::Native(|activation, this, _args| {
+                let count = this.get("count", activation)?.coerce_to_f64(activation)?;
+                this.set("count", (count + 1.0).into(), activation)?;
+                Ok(Value::Undefined)
+            }),
+            None,
+            activation.context.avm1.prototypes.function,
+        );
+        listener.set("count", 0.into(), activation)?;
+        listener.set("onResize", on_resize.into(), activation)?;
+
+        let broadcaster = activation
+            .context
+            .avm1
+            .global_object_cell()
+            .get("Stage", activation)?
+            .coerce_to_object(activation);
+        broadcaster.call_method("addListener", &[listener.into()], activation)?;
+        Ok(listener.into())
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn current_scale_exact_fit() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::ExactFit);
+            stage.set_viewport_size(&mut activation.context, 1100, 200, 1.0);
+
+            let (scale_x, scale_y) = stage.current_scale();
+            assert_ne!(scale_x, scale_y);
+            assert_eq!(scale_x, 2.0);
+            assert_eq!(scale_y, 0.5);
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn chrome_insets_reserve_viewport_space() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_viewport_size(&mut activation.context, 1100, 900, 1.0);
+            assert_eq!(stage.matrix().ty, Twips::from_pixels(50.0));
+
+            stage.set_chrome_insets(&mut activation.context, (0.0, 0.0, 100.0, 0.0));
+            let (scale_x, scale_y) = stage.current_scale();
+            assert_eq!((scale_x, scale_y), (2.0, 2.0));
+            assert_eq!(stage.matrix().ty, Twips::ZERO);
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn device_to_stage_honors_scale_factor() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::NoScale);
+            stage.set_viewport_size(&mut activation.context, 1100, 800, 2.0);
+            assert_eq!(stage.stage_size(), (550, 400));
+            assert_eq!(stage.device_to_stage(200.0, 100.0), (100.0, 50.0));
+            assert_eq!(stage.stage_to_device(100.0, 50.0), (200.0, 100.0));
+
+            stage.set_content_offset(&mut activation.context, 20.0, 10.0);
+            assert_eq!(stage.device_to_stage(220.0, 110.0), (100.0, 50.0));
+
+            for &scale_mode in &[StageScaleMode::ShowAll, StageScaleMode::ExactFit] {
+                stage.set_scale_mode(&mut activation.context, scale_mode);
+                stage.set_viewport_size(&mut activation.context, 1000, 1000, 2.0);
+                for &(x, y) in &[(0.0, 0.0), (137.5, 42.25), (550.0, 400.0)] {
+                    let (device_x, device_y) = stage.stage_to_device(x, y);
+                    let (stage_x, stage_y) = stage.device_to_stage(device_x, device_y);
+                    assert!((stage_x - x).abs() < 0.05);
+                    assert!((stage_y - y).abs() < 0.05);
+                }
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn pixel_snapping_rounds_view_translation() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::ShowAll);
+            // One pixel of horizontal space is split evenly on both sides.
+            stage.set_viewport_size(&mut activation.context, 551, 400, 1.0);
+            assert!(!stage.pixel_snapping());
+            assert_eq!(stage.matrix().tx, Twips::from_pixels(0.5));
+
+            stage.set_pixel_snapping(&mut activation.context, true);
+            assert_eq!(stage.matrix().tx, Twips::from_pixels(1.0));
+            assert_eq!(stage.matrix().ty, Twips::ZERO);
+
+            stage.set_pixel_snapping(&mut activation.context, false);
+            assert_eq!(stage.matrix().tx, Twips::from_pixels(0.5));
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn view_matrix_includes_scale_factor() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::NoScale);
+            stage.set_viewport_size(&mut activation.context, 1100, 800, 2.0);
+            stage.set_content_offset(&mut activation.context, 20.0, 10.0);
+
+            let transform = *stage.matrix();
+            assert_eq!((transform.a, transform.d), (2.0, 2.0));
+            assert_eq!(
+                transform * (Twips::from_pixels(100.0), Twips::from_pixels(50.0)),
+                (Twips::from_pixels(220.0), Twips::from_pixels(110.0))
+            );
+            assert_eq!(stage.stage_to_device(100.0, 50.0), (220.0, 110.0));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn set_background_color_requests_render() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            *activation.context.needs_render = false;
+            stage.set_background_color(
+                &mut activation.context,
+                Some(Color::from_rgb(0x336699, 255)),
+            );
+            assert!(*activation.context.needs_render);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn movie_declared_background_is_independent() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            let declared = Color::from_rgb(0xFF0000, 255);
+            let host = Color::from_rgb(0x0000FF, 255);
+            stage.set_movie_declared_background(
+                activation.context.gc_context,
+                Some(declared.clone()),
+            );
+            stage.set_background_color(&mut activation.context, Some(host.clone()));
+
+            assert_eq!(stage.movie_declared_background(), Some(declared));
+            assert_eq!(stage.background_color(), Some(host));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn visible_movie_rect_follows_content_offset() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::NoScale);
+
+            let rect = stage.visible_movie_rect();
+            assert_eq!(rect.x_min, Twips::ZERO);
+            assert_eq!(rect.y_min, Twips::ZERO);
+            assert_eq!(rect.x_max, Twips::from_pixels(550.0));
+            assert_eq!(rect.y_max, Twips::from_pixels(400.0));
+
+            stage.set_content_offset(&mut activation.context, 100.0, 50.0);
+            let rect = stage.visible_movie_rect();
+            assert_eq!(rect.x_min, Twips::from_pixels(-100.0));
+            assert_eq!(rect.y_min, Twips::from_pixels(-50.0));
+            assert_eq!(rect.x_max, Twips::from_pixels(450.0));
+            assert_eq!(rect.y_max, Twips::from_pixels(350.0));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn align_round_trip() {
+        for bits in 0..16 {
+            let align = StageAlign::from_bits_truncate(bits);
+            let parsed: StageAlign = align.to_string().parse().unwrap();
+            assert_eq!(parsed, align);
+        }
+        assert_eq!(StageAlign::empty().to_string(), "");
+        assert_eq!((StageAlign::LEFT | StageAlign::TOP).to_string(), "TL");
+        assert_eq!((StageAlign::RIGHT | StageAlign::BOTTOM).to_string(), "BR");
+        assert_eq!("lrbt".parse::<StageAlign>().unwrap().to_string(), "TBLR");
+    }
+
+    #[test]
+    fn display_state_round_trip() {
+        for &display_state in &[
+            StageDisplayState::Normal,
+            StageDisplayState::FullScreen,
+            StageDisplayState::FullScreenInteractive,
+        ] {
+            let parsed: StageDisplayState = display_state.to_string().parse().ok().unwrap();
+            assert_eq!(parsed, display_state);
+        }
+        assert!("maximized".parse::<StageDisplayState>().is_err());
+    }
+
+    #[test]
+    fn set_display_state_updates_state() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            assert_eq!(stage.display_state(), StageDisplayState::Normal);
+
+            stage.set_display_state(&mut activation.context, StageDisplayState::FullScreen);
+            assert_eq!(stage.display_state(), StageDisplayState::FullScreen);
+
+            stage.set_display_state(&mut activation.context, StageDisplayState::Normal);
+            assert_eq!(stage.display_state(), StageDisplayState::Normal);
+            Ok(())
+        });
+    }
+
+    /// A UI backend that, like web, enters fullscreen asynchronously.
+    ///
+    /// Requests are ignored; tests flip `fullscreen` to act as the host.
+    #[derive(Default)]
+    struct AsyncFullscreenUi {
+        fullscreen: bool,
+    }
+
+    impl UiBackend for AsyncFullscreenUi {
+        fn is_key_down(&self, _key: KeyCode) -> bool {
+            false
+        }
+        fn last_key_code(&self) -> KeyCode {
+            KeyCode::Unknown
+        }
+        fn last_key_char(&self) -> Option<char> {
+            None
+        }
+        fn mouse_visible(&self) -> bool {
+            true
+        }
+        fn set_mouse_visible(&mut self, _visible: bool) {}
+        fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
+        fn set_clipboard_content(&mut self, _content: String) {}
+        fn is_fullscreen(&self) -> bool {
+            self.fullscreen
+        }
+        fn set_fullscreen(&mut self, _is_full: bool) {}
+        fn display_unsupported_message(&self) {}
+        fn message(&self, _message: &str) {}
+    }
+
+    /// Sets whether the `AsyncFullscreenUi` in `context` is fullscreen.
+    fn set_host_fullscreen(context: &mut UpdateContext<'_, '_, '_>, fullscreen: bool) {
+        context
+            .ui
+            .downcast_mut::<AsyncFullscreenUi>()
+            .unwrap()
+            .fullscreen = fullscreen;
+    }
+
+    #[test]
+    fn display_state_follows_backend_leaving_fullscreen() {
+        let mut ui = AsyncFullscreenUi::default();
+        with_avm_and_ui(19, &mut ui, |activation, _this| -> Result<(), Error> {
+            let listener = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            let on_full_screen = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, this, args| {
+                    this.set("fullScreen", args[0], activation)?;
+                    Ok(Value::Undefined)
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            listener.set("onFullScreen", on_full_screen.into(), activation)?;
+            let broadcaster = activation
+                .context
+                .avm1
+                .global_object_cell()
+                .get("Stage", activation)?
+                .coerce_to_object(activation);
+            broadcaster.call_method("addListener", &[listener.into()], activation)?;
+
+            let stage = activation.context.stage;
+            stage.set_display_state(&mut activation.context, StageDisplayState::FullScreen);
+            assert_eq!(listener.get("fullScreen", activation)?, true.into());
+            assert_eq!(stage.display_state(), StageDisplayState::FullScreen);
+
+            // The host hasn't entered fullscreen yet, which isn't the user leaving it.
+            assert_eq!(
+                broadcaster.get("displayState", activation)?,
+                "fullScreen".into()
+            );
+            assert_eq!(listener.get("fullScreen", activation)?, true.into());
+
+            set_host_fullscreen(&mut activation.context, true);
+            assert_eq!(
+                broadcaster.get("displayState", activation)?,
+                "fullScreen".into()
+            );
+            assert_eq!(listener.get("fullScreen", activation)?, true.into());
+
+            // The user presses Esc.
+            set_host_fullscreen(&mut activation.context, false);
+            assert_eq!(
+                broadcaster.get("displayState", activation)?,
+                "normal".into()
+            );
+            assert_eq!(listener.get("fullScreen", activation)?, false.into());
+            assert_eq!(stage.display_state(), StageDisplayState::Normal);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn parse_quality() {
+        let cases = [
+            ("low", StageQuality::Low),
+            ("MEDIUM", StageQuality::Medium),
+            ("High", StageQuality::High),
+            ("best", StageQuality::Best),
+            ("8x8", StageQuality::High8x8),
+            ("8x8linear", StageQuality::High8x8Linear),
+            ("16X16", StageQuality::High16x16),
+            ("16x16LINEAR", StageQuality::High16x16Linear),
+        ];
+        for &(s, quality) in &cases {
+            assert_eq!(s.parse::<StageQuality>().ok(), Some(quality));
+            assert_eq!(
+                quality.to_string().parse::<StageQuality>().ok(),
+                Some(quality)
+            );
+        }
+        assert!("ultra".parse::<StageQuality>().is_err());
+    }
+
+    #[test]
+    fn quality_getter_returns_stored_value() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            assert_eq!(stage.quality(), StageQuality::High);
+
+            stage.set_quality(activation.context.gc_context, StageQuality::Low);
+            assert_eq!(stage.quality(), StageQuality::Low);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn render_passes_quality_to_renderer() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_quality(activation.context.gc_context, StageQuality::Low);
+            let renderer = render_with(
+                stage,
+                activation.context.library,
+                activation.context.focus_tracker,
+            );
+            assert_eq!(renderer.quality, Some(StageQuality::Low));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn resize_fires_when_stage_size_changes() {
+        for &scale_mode in &[
+            StageScaleMode::ExactFit,
+            StageScaleMode::NoBorder,
+            StageScaleMode::NoScale,
+            StageScaleMode::ShowAll,
+        ] {
+            with_avm(19, |activation, _this| -> Result<(), Error> {
+                let stage = activation.context.stage;
+                stage.set_scale_mode(&mut activation.context, scale_mode);
+                let listener = add_resize_listener(activation)?;
+
+                stage.set_viewport_size(&mut activation.context, 800, 600, 1.0);
+                let expected = if scale_mode == StageScaleMode::NoScale {
+                    1
+                } else {
+                    0
+                };
+                assert_eq!(
+                    listener.get("count", activation)?,
+                    expected.into(),
+                    "{}",
+                    scale_mode
+                );
+                Ok(())
+            });
+        }
+    }
+
+    #[test]
+    fn resize_fires_when_leaving_no_scale() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_viewport_size(&mut activation.context, 800, 600, 1.0);
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::NoScale);
+            let listener = add_resize_listener(activation)?;
+
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::ShowAll);
+            assert_eq!(listener.get("count", activation)?, 1.into());
+            assert_eq!(stage.stage_size(), (550, 400));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn scale_factor_change_fires_resize() {
+        for &scale_mode in &[StageScaleMode::ShowAll, StageScaleMode::NoScale] {
+            with_avm(19, |activation, _this| -> Result<(), Error> {
+                let stage = activation.context.stage;
+                stage.set_scale_mode(&mut activation.context, scale_mode);
+                stage.set_viewport_size(&mut activation.context, 800, 600, 1.0);
+                let listener = add_resize_listener(activation)?;
+
+                // Only the scale factor changes.
+                stage.set_viewport_size(&mut activation.context, 800, 600, 2.0);
+                assert_eq!(
+                    listener.get("count", activation)?,
+                    1.into(),
+                    "{}",
+                    scale_mode
+                );
+
+                stage.set_viewport_size(&mut activation.context, 800, 600, 2.0);
+                assert_eq!(
+                    listener.get("count", activation)?,
+                    1.into(),
+                    "{}",
+                    scale_mode
+                );
+                Ok(())
+            });
+        }
+    }
+
+    #[test]
+    fn resize_listener_sees_new_stage_size() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::NoScale);
+            let listener = add_resize_listener(activation)?;
+
+            // Record `Stage.width` and `Stage.height` as seen by the handler.
+            let on_resize = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|activation, this, _args| {
+                    let broadcaster = activation
+                        .context
+                        .avm1
+                        .global_object_cell()
+                        .get("Stage", activation)?
+                        .coerce_to_object(activation);
+                    let width = broadcaster.get("width", activation)?;
+                    let height = broadcaster.get("height", activation)?;
+                    this.set("width", width, activation)?;
+                    this.set("height", height, activation)?;
+                    Ok(Value::Undefined)
+                }),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            listener.set("onResize", on_resize.into(), activation)?;
+
+            stage.set_viewport_size(&mut activation.context, 640, 480, 1.0);
+            assert_eq!(listener.get("width", activation)?, 640.into());
+            assert_eq!(listener.get("height", activation)?, 480.into());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn is_visible_uses_view_bounds() {
+        fn bounds(x_min: f64, y_min: f64, x_max: f64, y_max: f64) -> BoundingBox {
+            BoundingBox {
+                x_min: Twips::from_pixels(x_min),
+                y_min: Twips::from_pixels(y_min),
+                x_max: Twips::from_pixels(x_max),
+                y_max: Twips::from_pixels(y_max),
+                valid: true,
+            }
+        }
+
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+
+            // The whole 550x400 movie is visible.
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::NoScale);
+            stage.set_viewport_size(&mut activation.context, 550, 400, 1.0);
+            assert!(stage.is_visible(&bounds(10.0, 10.0, 20.0, 20.0)));
+            assert!(stage.is_visible(&bounds(540.0, 390.0, 560.0, 410.0)));
+            assert!(!stage.is_visible(&bounds(600.0, 0.0, 700.0, 10.0)));
+            assert!(!stage.is_visible(&BoundingBox::default()));
+
+            // Scaled up 2x and centered, cropping 100px from the top and bottom.
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::NoBorder);
+            stage.set_viewport_size(&mut activation.context, 1100, 400, 1.0);
+            assert!(stage.is_visible(&bounds(10.0, 150.0, 20.0, 160.0)));
+            assert!(stage.is_visible(&bounds(10.0, 290.0, 20.0, 310.0)));
+            assert!(!stage.is_visible(&bounds(10.0, 10.0, 20.0, 20.0)));
+            assert!(!stage.is_visible(&bounds(10.0, 350.0, 20.0, 390.0)));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn draw_letterbox_uses_letterbox_color() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            let color = Color::from_rgb(0xFFFFFF, 255);
+            stage.set_letterbox_color(activation.context.gc_context, color.clone());
+            assert_eq!(stage.letterbox_color(), color);
+
+            // A taller viewport leaves bars above and below the movie.
+            stage.set_letterbox(activation.context.gc_context, Letterbox::On);
+            stage.set_viewport_size(&mut activation.context, 1100, 900, 1.0);
+
+            let renderer = render_with(
+                stage,
+                activation.context.library,
+                activation.context.focus_tracker,
+            );
+
+            assert_eq!(renderer.rect_colors, vec![color.clone(), color]);
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn full_screen_source_rect_fills_viewport() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            let rect = Rectangle {
+                x_min: Twips::from_pixels(100.0),
+                x_max: Twips::from_pixels(300.0),
+                y_min: Twips::from_pixels(50.0),
+                y_max: Twips::from_pixels(150.0),
+            };
+            stage.set_full_screen_source_rect(&mut activation.context, Some(rect.clone()));
+            stage.set_viewport_size(&mut activation.context, 800, 600, 1.0);
+
+            // The source rect is ignored until the stage goes fullscreen.
+            assert_eq!(stage.matrix().tx, Twips::ZERO);
+            assert_ne!(stage.current_scale(), (4.0, 4.0));
+
+            stage.set_display_state(&mut activation.context, StageDisplayState::FullScreen);
+            let matrix = stage.matrix();
+            assert_eq!(stage.current_scale(), (4.0, 4.0));
+            assert_eq!(matrix.tx, Twips::from_pixels(-400.0));
+            assert_eq!(matrix.ty, Twips::from_pixels(-100.0));
+            assert_eq!(stage.view_bounds(), rect.into());
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn align_offset_precedence() {
+        // Expected offsets for every combination of TOP (1), BOTTOM (2), LEFT (4), RIGHT (8),
+        // with 100 horizontal and 40 vertical pixels of leftover space.
+        let expected = [
+            (50.0, 20.0),  // ""
+            (50.0, 0.0),   // T
+            (50.0, 40.0),  // B
+            (50.0, 0.0),   // TB
+            (0.0, 20.0),   // L
+            (0.0, 0.0),    // TL
+            (0.0, 40.0),   // BL
+            (0.0, 0.0),    // TBL
+            (100.0, 20.0), // R
+            (100.0, 0.0),  // TR
+            (100.0, 40.0), // BR
+            (100.0, 0.0),  // TBR
+            (0.0, 20.0),   // LR
+            (0.0, 0.0),    // TLR
+            (0.0, 40.0),   // BLR
+            (0.0, 0.0),    // TBLR
+        ];
+        for (bits, &offset) in expected.iter().enumerate() {
+            let align = StageAlign::from_bits_truncate(bits as u8);
+            assert_eq!(align_offset(align, 100.0, 40.0), offset, "{:?}", align);
+        }
+
+        // Under ExactFit there is no leftover space, so alignment has no effect.
+        let all = StageAlign::TOP | StageAlign::BOTTOM | StageAlign::LEFT | StageAlign::RIGHT;
+        assert_eq!(align_offset(all, 0.0, 0.0), (0.0, 0.0));
+        assert_eq!(align_offset(StageAlign::empty(), 0.0, 0.0), (0.0, 0.0));
+    }
+
+    /// Renders the stage, returning the recorded draw calls.
+    fn render_with<'gc>(
+        stage: Stage<'gc>,
+        library: &Library<'gc>,
+        focus_tracker: FocusTracker<'gc>,
+    ) -> ColorRecorder {
+        let mut renderer = ColorRecorder::default();
+        let mut render_context = RenderContext {
+            renderer: &mut renderer,
+            ui: &mut NullUiBackend::new(),
+            library,
+            transform_stack: &mut TransformStack::new(),
+            stage,
+            clip_depth_stack: vec![],
+            allow_mask: true,
+            quality: stage.quality(),
+            focus_tracker,
+        };
+        stage.render(&mut render_context);
+        renderer
+    }
+
+    /// Draws a filled 100x100 square into the given clip.
+    fn draw_square<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Avm1Object<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        this.call_method("beginFill", &[0xFF0000.into()], activation)?;
+        this.call_method("moveTo", &[0.into(), 0.into()], activation)?;
+        this.call_method("lineTo", &[100.into(), 0.into()], activation)?;
+        this.call_method("lineTo", &[100.into(), 100.into()], activation)?;
+        this.call_method("lineTo", &[0.into(), 100.into()], activation)?;
+        this.call_method("endFill", &[], activation)?;
+        Ok(())
+    }
+
+    #[test]
+    fn render_clips_to_viewport_under_no_border() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_viewport_size(&mut activation.context, 1100, 400, 1.0);
+
+            // Under `ShowAll`, content can't overflow the viewport, so nothing is clipped.
+            assert_eq!(stage.viewport_clip_matrix(), None);
+            let renderer = render_with(
+                stage,
+                activation.context.library,
+                activation.context.focus_tracker,
+            );
+            assert!(renderer.mask_rects.is_empty());
+
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::NoBorder);
+            let viewport = Matrix::create_box(1100.0, 400.0, 0.0, Twips::ZERO, Twips::ZERO);
+            assert_eq!(stage.viewport_clip_matrix(), Some(viewport));
+
+            let renderer = render_with(
+                stage,
+                activation.context.library,
+                activation.context.focus_tracker,
+            );
+
+            // The clip is drawn once to push the mask, and again to pop it.
+            assert_eq!(renderer.mask_rects, vec![viewport, viewport]);
+            assert!(!renderer.drawing_mask);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn stage_color_transform_applies_to_children() {
+        with_avm(19, |activation, this| -> Result<(), Error> {
+            draw_square(activation, this)?;
+
+            let stage = activation.context.stage;
+            let tint = ColorTransform {
+                r_mult: Fixed8::from_f32(0.5),
+                r_add: 16,
+                ..Default::default()
+            };
+            stage.set_color_transform(activation.context.gc_context, &tint);
+            assert_eq!(*stage.color_transform(), tint);
+
+            let renderer = render_with(
+                stage,
+                activation.context.library,
+                activation.context.focus_tracker,
+            );
+
+            assert_eq!(renderer.shape_color_transforms, vec![tint]);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn set_and_get_levels() {
+        with_avm(19, |activation, this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            let root = this.as_display_object().unwrap();
+            let movie = root.movie().unwrap();
+            let gc_context = activation.context.gc_context;
+            let clips: Vec<DisplayObject<'_>> = (0..3)
+                .map(|_| MovieClip::new(SwfSlice::empty(movie.clone()), gc_context).into())
+                .collect();
+            let (level_5, level_10, replacement) = (clips[0], clips[1], clips[2]);
+
+            assert!(DisplayObject::ptr_eq(stage.get_level(0).unwrap(), root));
+            stage.set_level(&mut activation.context, 10, level_10);
+            stage.set_level(&mut activation.context, 5, level_5);
+            assert!(stage.get_level(1).is_none());
+            assert!(DisplayObject::ptr_eq(stage.get_level(5).unwrap(), level_5));
+            assert!(DisplayObject::ptr_eq(
+                stage.get_level(10).unwrap(),
+                level_10
+            ));
+            assert_eq!(level_5.depth(), 5);
+            let depths: Vec<_> = stage
+                .iter_render_list()
+                .map(|level| level.depth())
+                .collect();
+            assert_eq!(depths, vec![0, 5, 10]);
+
+            // Replacing a level removes the movie that was there.
+            stage.set_level(&mut activation.context, 5, replacement);
+            assert!(DisplayObject::ptr_eq(
+                stage.get_level(5).unwrap(),
+                replacement
+            ));
+            assert!(!stage
+                .iter_render_list()
+                .any(|level| DisplayObject::ptr_eq(level, level_5)));
+            assert_eq!(stage.num_children(), 3);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn mouse_enabled_controls_mouse_pick() {
+        with_avm(19, |activation, this| -> Result<(), Error> {
+            draw_square(activation, this)?;
+            let on_release = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(|_activation, _this, _args| Ok(Value::Undefined)),
+                None,
+                activation.context.avm1.prototypes.function,
+            );
+            this.set("onRelease", on_release.into(), activation)?;
+
+            let stage = activation.context.stage;
+            let root = this.as_display_object().unwrap();
+            let pos = (Twips::from_pixels(50.0), Twips::from_pixels(50.0));
+            assert!(stage.mouse_enabled());
+            let picked = stage.mouse_pick(&mut activation.context, stage.into(), pos);
+            assert!(picked.map_or(false, |picked| DisplayObject::ptr_eq(picked, root)));
+
+            stage.set_mouse_enabled(activation.context.gc_context, false);
+            let picked = stage.mouse_pick(&mut activation.context, stage.into(), pos);
+            assert!(picked.is_none());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn stage_focus_rect_controls_focus_highlight() {
+        with_avm(19, |activation, this| -> Result<(), Error> {
+            // Give the root clip some bounds to highlight.
+            draw_square(activation, this)?;
+
+            let stage = activation.context.stage;
+            let focus_tracker = activation.context.focus_tracker;
+            focus_tracker.set(this.as_display_object(), &mut activation.context);
+
+            let highlight = Color::from_rgb(0xFFFF00, 255);
+            assert!(stage.stage_focus_rect());
+            let renderer = render_with(stage, activation.context.library, focus_tracker);
+            assert!(renderer.rect_colors.contains(&highlight));
+
+            stage.set_stage_focus_rect(activation.context.gc_context, false);
+            let renderer = render_with(stage, activation.context.library, focus_tracker);
+            assert!(!renderer.rect_colors.contains(&highlight));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn begin_frame_preserves_background_alpha() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            let color = Color::from_rgb(0x336699, 0x80);
+            stage.set_background_color(&mut activation.context, Some(color.clone()));
+
+            // An opaque stage ignores the alpha of the background color.
+            let clear_color = render_with(
+                stage,
+                activation.context.library,
+                activation.context.focus_tracker,
+            )
+            .clear_color
+            .unwrap();
+            assert_eq!(clear_color, Color::from_rgb(0x336699, 255));
+
+            stage.set_transparent_background(activation.context.gc_context, true);
+            let clear_color = render_with(
+                stage,
+                activation.context.library,
+                activation.context.focus_tracker,
+            )
+            .clear_color
+            .unwrap();
+            assert_eq!(clear_color, color);
+            Ok(())
+        });
+    }
+
+    /// Builds a root clip for a movie whose header declares the given background color.
+    fn clip_with_background<'gc>(
+        gc_context: MutationContext<'gc, '_>,
+        color: Option<Color>,
+    ) -> DisplayObject<'gc> {
+        let header = swf::Header::default_with_swf_version(10);
+        let tags: Vec<_> = color
+            .into_iter()
+            .map(swf::Tag::SetBackgroundColor)
+            .collect();
+        let mut data = Vec::new();
+        swf::write_swf(&header, &tags, &mut data).unwrap();
+        let movie = SwfMovie::from_data(&data, None, None).unwrap();
+        MovieClip::new(SwfSlice::empty(Arc::new(movie)), gc_context).into()
+    }
+
+    #[test]
+    fn background_color_from_movie_header() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            let red = Color::from_rgb(0xFF0000, 255);
+            let green = Color::from_rgb(0x00FF00, 255);
+
+            let clip = clip_with_background(activation.context.gc_context, Some(red.clone()));
+            stage.set_level(&mut activation.context, 0, clip);
+            assert_eq!(stage.background_color(), Some(red.clone()));
+            assert_eq!(stage.movie_declared_background(), Some(red.clone()));
+
+            // Other levels don't affect the background.
+            let clip = clip_with_background(activation.context.gc_context, Some(green.clone()));
+            stage.set_level(&mut activation.context, 1, clip);
+            assert_eq!(stage.background_color(), Some(red));
+
+            // Replacing `_level0` resets the background to the new movie's.
+            let clip = clip_with_background(activation.context.gc_context, Some(green.clone()));
+            stage.set_level(&mut activation.context, 0, clip);
+            assert_eq!(stage.background_color(), Some(green));
+
+            let clip = clip_with_background(activation.context.gc_context, None);
+            stage.set_level(&mut activation.context, 0, clip);
+            assert_eq!(stage.background_color(), None);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn background_color_from_movie_header_keeps_host_color() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            let host = Color::from_rgb(0x0000FF, 255);
+            stage.set_background_color(&mut activation.context, Some(host.clone()));
+
+            let red = Some(Color::from_rgb(0xFF0000, 255));
+            let clip = clip_with_background(activation.context.gc_context, red);
+            stage.set_level(&mut activation.context, 0, clip);
+            assert_eq!(stage.background_color(), Some(host));
+            Ok(())
+        });
+    }
+
+    /// A log backend that counts the messages traced by the movie.
+    struct TraceCounter(Rc<Cell<usize>>);
+
+    impl LogBackend for TraceCounter {
+        fn avm_trace(&self, _message: &str) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn invalidate_fires_render_event_once() {
+        let traces = Rc::new(Cell::new(0));
+        let player = Player::new(
+            Box::new(NullRenderer::new()),
+            Box::new(NullAudioBackend::new()),
+            Box::new(NullNavigatorBackend::new()),
+            Box::new(MemoryStorageBackend::default()),
+            Box::new(NullLocaleBackend::new()),
+            Box::new(NullVideoBackend::new()),
+            Box::new(TraceCounter(traces.clone())),
+            Box::new(NullUiBackend::new()),
+        )
+        .unwrap();
+        let mut player = player.lock().unwrap();
+
+        let header = swf::Header::default_with_swf_version(10);
+        let tags = [swf::Tag::FileAttributes(
+            swf::FileAttributes::IS_ACTION_SCRIPT_3,
+        )];
+        let mut data = Vec::new();
+        swf::write_swf(&header, &tags, &mut data).unwrap();
+        player.set_root_movie(Arc::new(SwfMovie::from_data(&data, None, None).unwrap()));
+
+        // Trace every render event that reaches the stage.
+        player
+            .update(|context| {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let trace = activation.context.avm2.global_domain().get_defined_value(
+                    &mut activation,
+                    Avm2QName::new(Avm2Namespace::public(), "trace"),
+                )?;
+                let mut stage = activation
+                    .context
+                    .stage
+                    .object2()
+                    .coerce_to_object(&mut activation)?;
+                stage
+                    .get_property(
+                        stage,
+                        &Avm2QName::new(Avm2Namespace::public(), "addEventListener"),
+                        &mut activation,
+                    )?
+                    .coerce_to_object(&mut activation)?
+                    .call(
+                        Some(stage),
+                        &["render".into(), trace],
+                        &mut activation,
+                        None,
+                    )
+                    .map(|_| ())
+            })
+            .unwrap();
+
+        player.run_frame();
+        assert_eq!(traces.get(), 0);
+
+        // Multiple invalidations before a frame only cause one render event.
+        player.update(|context| {
+            let stage = context.stage;
+            stage.invalidate(context);
+            stage.invalidate(context);
+        });
+        player.run_frame();
+        assert_eq!(traces.get(), 1);
+
+        player.run_frame();
+        assert_eq!(traces.get(), 1);
+    }
+
+    #[test]
+    fn mouse_to_local_exact_fit() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::ExactFit);
+            stage.set_viewport_size(&mut activation.context, 1100, 200, 1.0);
+
+            assert_eq!(
+                stage.mouse_to_local((Twips::ZERO, Twips::ZERO)),
+                (Twips::ZERO, Twips::ZERO)
+            );
+            assert_eq!(
+                stage.mouse_to_local((Twips::from_pixels(1100.0), Twips::from_pixels(200.0))),
+                (Twips::from_pixels(550.0), Twips::from_pixels(400.0))
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn frame_rate_is_clamped() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_frame_rate(&mut activation.context, 30.0);
+            assert_eq!(stage.frame_rate(&activation.context), 30.0);
+            assert_eq!(*activation.context.frame_rate, 30.0);
+
+            stage.set_frame_rate(&mut activation.context, 0.0);
+            assert_eq!(stage.frame_rate(&activation.context), 0.01);
+
+            stage.set_frame_rate(&mut activation.context, 5000.0);
+            assert_eq!(stage.frame_rate(&activation.context), 1000.0);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn no_scale_stage_size_is_stable_with_fractional_scale() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            stage.set_scale_mode(&mut activation.context, StageScaleMode::NoScale);
+            let cases = [
+                ((1000, 750), (800, 600)),
+                ((1001, 751), (800, 600)),
+                ((1003, 753), (802, 602)),
+                ((1004, 754), (803, 603)),
+                ((1005, 755), (804, 604)),
+            ];
+            for &((viewport_width, viewport_height), stage_size) in &cases {
+                stage.set_viewport_size(
+                    &mut activation.context,
+                    viewport_width,
+                    viewport_height,
+                    1.25,
+                );
+                assert_eq!(stage.stage_size(), stage_size);
+            }
+
+            // Resizing back and forth yields the same sizes, without spurious resize events.
+            let listener = add_resize_listener(activation)?;
+            for &((viewport_width, viewport_height), stage_size) in cases.iter().rev() {
+                stage.set_viewport_size(
+                    &mut activation.context,
+                    viewport_width,
+                    viewport_height,
+                    1.25,
+                );
+                assert_eq!(stage.stage_size(), stage_size);
+                stage.set_viewport_size(
+                    &mut activation.context,
+                    viewport_width,
+                    viewport_height,
+                    1.25,
+                );
+            }
+            // 1005 -> 1004 -> 1003 -> 1001 -> 1000: only 1001 -> 1000 keeps the same stage size.
+            assert_eq!(listener.get("count", activation)?, 3.into());
+
+            stage.set_viewport_size(&mut activation.context, 330, 330, 1.1);
+            assert_eq!(stage.stage_size(), (300, 300));
+            Ok(())
+        });
+    }
+
+    /// Builds an AVM1 `ContextMenu`-like object with a single custom item.
+    fn menu_with_custom_item<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Avm1Object<'gc>, Error<'gc>> {
+        let gc_context = activation.context.gc_context;
+        let prototypes = &activation.context.avm1.prototypes;
+        let item = ScriptObject::object(gc_context, Some(prototypes.object));
+        let on_select = FunctionObject::function(
+            gc_context,
+            Executable::Native(|_activation, _this, _args| Ok(Value::Undefined)),
+            None,
+            prototypes.function,
+        );
+        let custom_items = ScriptObject::array(gc_context, Some(prototypes.array));
+        let menu = ScriptObject::object(gc_context, Some(prototypes.object));
+
+        item.set("caption", "Custom".into(), activation)?;
+        item.set("onSelect", on_select.into(), activation)?;
+        custom_items.set_array_element(0, item.into(), gc_context);
+        menu.set("customItems", custom_items.into(), activation)?;
+        Ok(menu.into())
+    }
+
+    /// The captions of the items in a context menu.
+    fn captions(menu: &ContextMenuState<'_>) -> Vec<String> {
+        menu.info()
+            .iter()
+            .map(|item| item.caption.clone())
+            .collect()
+    }
+
+    #[test]
+    fn context_menu_respects_show_menu() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            let menu = menu_with_custom_item(activation)?;
+
+            let default_menu = stage.build_context_menu(activation, None);
+            assert_eq!(captions(&default_menu), vec!["High Quality"]);
+            let full_menu = stage.build_context_menu(activation, Some(menu));
+            assert_eq!(captions(&full_menu), vec!["High Quality", "Custom"]);
+
+            stage.set_show_menu(&mut activation.context, false);
+            let default_menu = stage.build_context_menu(activation, None);
+            assert!(default_menu.info().is_empty());
+            let custom_menu = stage.build_context_menu(activation, Some(menu));
+            assert_eq!(captions(&custom_menu), vec!["Custom"]);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn context_menu_hide_builtin_items_overrides_show_menu() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let stage = activation.context.stage;
+            let menu = menu_with_custom_item(activation)?;
+            let built_in_items = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.object),
+            );
+            menu.set("builtInItems", built_in_items.into(), activation)?;
+            crate::avm1::globals::context_menu::hide_builtin_items(activation, menu, &[])?;
+
+            assert!(stage.show_menu());
+            let custom_menu = stage.build_context_menu(activation, Some(menu));
+            assert_eq!(captions(&custom_menu), vec!["Custom"]);
+            let default_menu = stage.build_context_menu(activation, None);
+            assert_eq!(captions(&default_menu), vec!["High Quality"]);
+            Ok(())
+        });
+    }
+}
@@ -405,7 +405,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
     }
 
     fn render(&self, context: &mut RenderContext) {
-        if !self.world_bounds().intersects(&context.stage.view_bounds()) {
+        if !context.stage.is_visible(&self.world_bounds()) {
             // Off-screen; culled
             return;
         }
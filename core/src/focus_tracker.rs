@@ -1,6 +1,7 @@
 use crate::avm1::{Avm1, Value};
-use crate::context::UpdateContext;
+use crate::context::{RenderContext, UpdateContext};
 pub use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::prelude::*;
 use gc_arena::{Collect, GcCell, MutationContext};
 
 #[derive(Clone, Copy, Collect, Debug)]
@@ -56,4 +57,37 @@ impl<'gc> FocusTracker<'gc> {
             ],
         );
     }
+
+    /// Draw a highlight around the focused object, unless disabled by `Stage.stageFocusRect`.
+    pub fn render_highlight(&self, context: &mut RenderContext<'_, 'gc>) {
+        if !context.stage.stage_focus_rect() {
+            return;
+        }
+        let focus = match self.get() {
+            // Text fields show their cursor instead.
+            Some(focus) if focus.as_edit_text().is_none() => focus,
+            _ => return,
+        };
+        let bounds = focus.world_bounds().transform(&context.stage.matrix());
+        if !bounds.valid {
+            return;
+        }
+
+        const THICKNESS: f32 = 2.0;
+        let color = Color::from_rgb(0xFFFF00, 255);
+        let x = bounds.x_min;
+        let y = bounds.y_min;
+        let width = (bounds.x_max - bounds.x_min).to_pixels() as f32;
+        let height = (bounds.y_max - bounds.y_min).to_pixels() as f32;
+        let right = bounds.x_max - Twips::from_pixels(THICKNESS.into());
+        let bottom = bounds.y_max - Twips::from_pixels(THICKNESS.into());
+        for matrix in &[
+            Matrix::create_box(width, THICKNESS, 0.0, x, y),
+            Matrix::create_box(width, THICKNESS, 0.0, x, bottom),
+            Matrix::create_box(THICKNESS, height, 0.0, x, y),
+            Matrix::create_box(THICKNESS, height, 0.0, right, y),
+        ] {
+            context.renderer.draw_rect(color.clone(), matrix);
+        }
+    }
 }
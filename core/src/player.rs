@@ -18,7 +18,7 @@ use crate::backend::{
 use crate::config::Letterbox;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{ContextMenuCallback, ContextMenuItem, ContextMenuState};
-use crate::display_object::{EditText, MorphShape, MovieClip, Stage};
+use crate::display_object::{EditText, MorphShape, MovieClip, Stage, StageQuality};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent};
 use crate::external::Value as ExternalValue;
 use crate::external::{ExternalInterface, ExternalInterfaceProvider};
@@ -410,7 +410,7 @@ impl Player {
 
             root.post_instantiation(context, root, flashvars, Instantiator::Movie, false);
             root.set_default_root_name(context);
-            context.stage.replace_at_depth(context, root, 0);
+            context.stage.set_level(context, 0, root);
 
             // Load and parse the device font.
             if context.library.device_font().is_none() {
@@ -561,10 +561,6 @@ impl Player {
 
     pub fn prepare_context_menu(&mut self) -> Vec<ContextMenuItem> {
         self.mutate_with_update_context(|context| {
-            if !context.stage.show_menu() {
-                return vec![];
-            }
-
             let mut activation = Activation::from_stub(
                 context.reborrow(),
                 ActivationIdentifier::root("[ContextMenu]"),
@@ -584,10 +580,10 @@ impl Player {
                 }
             };
 
-            let menu = crate::avm1::globals::context_menu::make_context_menu_state(
-                menu_object,
-                &mut activation,
-            );
+            let menu = activation
+                .context
+                .stage
+                .build_context_menu(&mut activation, menu_object);
             let ret = menu.info().clone();
             *activation.context.current_context_menu = Some(menu);
             ret
@@ -609,6 +605,7 @@ impl Player {
                     ContextMenuCallback::Avm1 { item, callback } => {
                         Self::run_context_menu_custom_callback(*item, *callback, context)
                     }
+                    ContextMenuCallback::Quality => Self::toggle_quality(context),
                     ContextMenuCallback::Play => Self::toggle_play_root_movie(context),
                     ContextMenuCallback::Forward => Self::forward_root_movie(context),
                     ContextMenuCallback::Back => Self::back_root_movie(context),
@@ -654,6 +651,15 @@ impl Player {
         );
     }
 
+    fn toggle_quality<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let quality = if context.stage.quality() == StageQuality::Low {
+            StageQuality::High
+        } else {
+            StageQuality::Low
+        };
+        context.stage.set_quality(context.gc_context, quality);
+    }
+
     fn toggle_play_root_movie<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) {
         if let Some(mc) = context.stage.root_clip().as_movie_clip() {
             if mc.playing() {
@@ -697,11 +703,24 @@ impl Player {
         self.mutate_with_update_context(|context| context.stage.background_color())
     }
 
+    /// The background color declared by the movie, ignoring any override set by the host.
+    pub fn movie_declared_background(&mut self) -> Option<Color> {
+        self.mutate_with_update_context(|context| context.stage.movie_declared_background())
+    }
+
     pub fn set_background_color(&mut self, color: Option<Color>) {
+        self.mutate_with_update_context(|context| {
+            let stage = context.stage;
+            stage.set_background_color(context, color);
+        })
+    }
+
+    /// Sets whether the stage background may be transparent, as in the `transparent` window mode.
+    pub fn set_transparent_background(&mut self, value: bool) {
         self.mutate_with_update_context(|context| {
             context
                 .stage
-                .set_background_color(context.gc_context, color)
+                .set_transparent_background(context.gc_context, value)
         })
     }
 
@@ -744,8 +763,6 @@ impl Player {
 
     pub fn handle_event(&mut self, event: PlayerEvent) {
         let mut needs_render = self.needs_render;
-        let inverse_view_matrix =
-            self.mutate_with_update_context(|context| context.stage.inverse_view_matrix());
 
         if cfg!(feature = "avm_debug") {
             if let PlayerEvent::KeyDown {
@@ -812,7 +829,11 @@ impl Player {
         | PlayerEvent::MouseDown { x, y }
         | PlayerEvent::MouseUp { x, y } = event
         {
-            self.mouse_pos = inverse_view_matrix * (Twips::from_pixels(x), Twips::from_pixels(y));
+            self.mouse_pos = self.mutate_with_update_context(|context| {
+                context
+                    .stage
+                    .mouse_to_local((Twips::from_pixels(x), Twips::from_pixels(y)))
+            });
             if self.update_roll_over() {
                 needs_render = true;
             }
@@ -988,15 +1009,8 @@ impl Player {
         let mut new_cursor = self.mouse_cursor;
         let hover_changed = self.mutate_with_update_context(|context| {
             // Check hovered object.
-            let mut new_hovered = None;
-            let levels: Vec<_> = context.stage.iter_depth_list().collect();
-            for (_depth, level) in levels.iter().rev() {
-                if new_hovered.is_none() {
-                    new_hovered = level.mouse_pick(context, *level, mouse_pos);
-                } else {
-                    break;
-                }
-            }
+            let stage = context.stage;
+            let new_hovered = stage.mouse_pick(context, stage.into(), mouse_pos);
 
             let cur_hovered = context.mouse_hovered_object;
 
@@ -1071,6 +1085,7 @@ impl Player {
             stage.frame_constructed(update_context);
             stage.run_frame(update_context);
             stage.run_frame_scripts(update_context);
+            stage.fire_render_event(update_context);
 
             update_context.update_sounds();
         });
@@ -1091,6 +1106,8 @@ impl Player {
                 stage: root_data.stage,
                 clip_depth_stack: vec![],
                 allow_mask: true,
+                quality: root_data.stage.quality(),
+                focus_tracker: root_data.focus_tracker,
             };
 
             root_data.stage.render(&mut render_context);
@@ -5,7 +5,7 @@ use std::collections::HashSet;
 use std::rc::Rc;
 use tinyfiledialogs::{message_box_ok, MessageBoxIcon};
 use winit::event::{ElementState, ModifiersState, VirtualKeyCode, WindowEvent};
-use winit::window::Window;
+use winit::window::{Fullscreen, Window};
 
 pub struct DesktopUiBackend {
     window: Rc<Window>,
@@ -224,6 +224,14 @@ impl UiBackend for DesktopUiBackend {
         self.window.fullscreen().is_some()
     }
 
+    fn set_fullscreen(&mut self, is_full: bool) {
+        self.window.set_fullscreen(if is_full {
+            Some(Fullscreen::Borderless(None))
+        } else {
+            None
+        });
+    }
+
     fn display_unsupported_message(&self) {
         message_box_ok(
             "Ruffle - Unsupported content",
@@ -1,7 +1,7 @@
 use ruffle_core::backend::render::{
     swf::{self, CharacterId, GradientInterpolation, GradientSpread},
     Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, JpegTagFormat, MovieLibrary,
-    RenderBackend, ShapeHandle, Transform,
+    RenderBackend, ShapeHandle, StageQuality, Transform,
 };
 use ruffle_core::color_transform::ColorTransform;
 use ruffle_core::matrix::Matrix;
@@ -516,6 +516,12 @@ impl RenderBackend for WebCanvasRenderBackend {
         })
     }
 
+    fn set_quality(&mut self, quality: StageQuality) {
+        // Low quality doesn't smooth bitmaps.
+        self.context
+            .set_image_smoothing_enabled(quality != StageQuality::Low);
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         // Reset canvas transform in case it was left in a dirty state.
         self.context.reset_transform().unwrap();
@@ -523,7 +529,16 @@ impl RenderBackend for WebCanvasRenderBackend {
         let width = self.canvas.width();
         let height = self.canvas.height();
 
-        let color = format!("rgb({}, {}, {})", clear.r, clear.g, clear.b);
+        // Clear first so that a transparent background shows the page behind the canvas.
+        self.context
+            .clear_rect(0.0, 0.0, width.into(), height.into());
+        let color = format!(
+            "rgba({}, {}, {}, {})",
+            clear.r,
+            clear.g,
+            clear.b,
+            f64::from(clear.a) / 255.0
+        );
         self.context.set_fill_style(&color.into());
         self.context
             .fill_rect(0.0, 0.0, width.into(), height.into());
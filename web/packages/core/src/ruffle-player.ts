@@ -654,6 +654,19 @@ export class RufflePlayer extends HTMLElement {
         }
     }
 
+    /**
+     * Enters or exits fullscreen, as requested by the movie.
+     *
+     * @param isFull Whether the player should become fullscreen.
+     */
+    setFullscreen(isFull: boolean): void {
+        if (isFull) {
+            this.enterFullscreen();
+        } else {
+            this.exitFullscreen();
+        }
+    }
+
     private pointerDown(event: PointerEvent): void {
         // Disable context menu when touch support is being used
         // to avoid a long press triggering the context menu. (#1972)
@@ -113,6 +113,9 @@ extern "C" {
     #[wasm_bindgen(method, getter, js_name = "isFullscreen")]
     fn is_fullscreen(this: &JavascriptPlayer) -> bool;
 
+    #[wasm_bindgen(method, js_name = "setFullscreen")]
+    fn set_fullscreen(this: &JavascriptPlayer, is_full: bool);
+
     #[wasm_bindgen(method, js_name = "setMetadata")]
     fn set_metadata(this: &JavascriptPlayer, metadata: JsValue);
 }
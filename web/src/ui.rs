@@ -202,6 +202,10 @@ impl UiBackend for WebUiBackend {
         self.js_player.is_fullscreen()
     }
 
+    fn set_fullscreen(&mut self, is_full: bool) {
+        self.js_player.set_fullscreen(is_full)
+    }
+
     fn display_unsupported_message(&self) {
         self.js_player.display_unsupported_message()
     }